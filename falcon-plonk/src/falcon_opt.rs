@@ -1,7 +1,9 @@
-use crate::poly::{enforce_leq_765, mod_q, DualPolyVar, NTTPolyVar};
+use crate::poly::{
+    enforce_leq_765, enforce_less_than_norm_bound, l2_norm_var, mod_q, DualPolyVar, NTTPolyVar,
+};
 use ark_ff::PrimeField;
 use falcon_rust::{
-    DualPolynomial, NTTPolynomial, Polynomial, PublicKey, Signature, LOG_N, MODULUS, N,
+    power_of_q_field, DualPolynomial, NTTPolynomial, Polynomial, PublicKey, Signature, MODULUS, N,
 };
 use jf_plonk::{
     circuit::{Circuit, PlonkCircuit},
@@ -20,7 +22,8 @@ impl FalconNTTVerificationWitness {
         Self { pk, msg, sig }
     }
 
-    /// Falcon verification circuit. TOTAL cost: 50178
+    /// Falcon verification circuit.
+    /// TOTAL cost: 50178, plus the l2 norm computation and bound check
     pub fn verification_circuit<F: PrimeField>(
         &self,
         cs: &mut PlonkCircuit<F>,
@@ -33,9 +36,7 @@ impl FalconNTTVerificationWitness {
         let pk_poly: Polynomial = (&self.pk).into();
 
         // the [q, 2*q^2, 4 * q^3, ..., 2^9 * q^10] constant wires
-        let const_q_power: Vec<F> = (1..LOG_N + 2)
-            .map(|x| F::from(1u32 << (x - 1)) * F::from(MODULUS).pow(&[x as u64]))
-            .collect();
+        let const_q_power: Vec<F> = power_of_q_field::<F>();
 
         // ========================================
         // compute related data in the clear
@@ -123,6 +124,15 @@ impl FalconNTTVerificationWitness {
         for e in v_dual_poly_vars.neg.coeff.iter() {
             enforce_leq_765(cs, e)?;
         }
+
+        // ========================================
+        // proving l2_norm(v | sig) < SIG_L2_BOUND
+        // ========================================
+        // the infinity norm alone is a weaker statement than
+        // Falcon verification, which bounds the l2 norm
+        let l2_norm_var = l2_norm_var(cs, &sig_dual_poly_vars, &v_dual_poly_vars)?;
+        enforce_less_than_norm_bound(cs, &l2_norm_var)?;
+
         println!("norm: {} {}", sig_poly.infinity_norm(), v.infinity_norm());
         #[cfg(feature = "print-trace")]
         println!(
@@ -151,6 +161,7 @@ mod tests {
                 .secret_key
                 .sign_with_seed("test seed".as_ref(), message.as_ref());
 
+            #[cfg(feature = "ffi")]
             assert!(keypair.public_key.verify(message.as_ref(), &sig));
             assert!(keypair.public_key.verify_rust(message.as_ref(), &sig));
 
@@ -211,4 +222,34 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_opt_verification_over_norm() -> Result<(), PlonkError> {
+        let message = "testing message".as_bytes();
+        let (keypair, sig) = gen_sig_for_testing();
+        let bad_sig = sig.over_norm_for_testing(&keypair.public_key, message);
+        assert!(!keypair.public_key.verify_rust(message, &bad_sig));
+
+        let mut cs = PlonkCircuit::<Fq>::new_ultra_plonk(8);
+        let falcon_witness = FalconNTTVerificationWitness::build_witness(
+            keypair.public_key,
+            message.to_vec(),
+            bad_sig,
+        );
+        falcon_witness.verification_circuit(&mut cs)?;
+
+        // the public inputs are the same as for the valid signature:
+        // the inflated signature keeps its nonce
+        let mut public_inputs = vec![];
+        let pk_poly: Polynomial = (&keypair.public_key).into();
+        for &e in NTTPolynomial::from(&pk_poly).coeff() {
+            public_inputs.push(Fq::from(e));
+        }
+        let hm = Polynomial::from_hash_of_message(message, bad_sig.nonce());
+        for &e in NTTPolynomial::from(&hm).coeff() {
+            public_inputs.push(Fq::from(e));
+        }
+        assert!(cs.check_circuit_satisfiability(&public_inputs).is_err());
+        Ok(())
+    }
 }
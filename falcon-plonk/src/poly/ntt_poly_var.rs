@@ -20,8 +20,26 @@ impl<F: PrimeField> NTTPolyVar<F> {
 
     /// allocate variables for a private polynomial
     pub fn alloc_vars(cs: &mut PlonkCircuit<F>, poly: &NTTPolynomial) -> Result<Self, PlonkError> {
+        Self::alloc_vars_from_slice(cs, poly.coeff())
+    }
+
+    /// allocate variables for a private polynomial given by the slice
+    /// of its N NTT coefficients; returns an error if the slice does
+    /// not have length N
+    pub fn alloc_vars_from_slice(
+        cs: &mut PlonkCircuit<F>,
+        coeff: &[u16],
+    ) -> Result<Self, PlonkError> {
+        if coeff.len() != N {
+            return Err(PlonkError::InvalidParameters(format!(
+                "input length {} is not N = {}",
+                coeff.len(),
+                N
+            )));
+        }
+
         let mut res = vec![];
-        for &e in poly.coeff() {
+        for &e in coeff {
             res.push(cs.create_variable(F::from(e))?);
         }
 
@@ -71,6 +89,13 @@ impl<F: PrimeField> NTTPolyVar<F> {
         if input.coeff().len() != N {
             panic!("input length {} is not N", input.coeff().len())
         }
+        if power_of_q_s.len() < LOG_N + 1 {
+            return Err(PlonkError::InvalidParameters(format!(
+                "power_of_q_s length {} is less than LOG_N + 1 = {}",
+                power_of_q_s.len(),
+                LOG_N + 1
+            )));
+        }
         let mut output = input.coeff().to_vec();
 
         let param: Vec<F> = NTT_TABLE.iter().take(N).map(|&x| F::from(x)).collect();
@@ -183,9 +208,8 @@ mod tests {
 
     use super::*;
     use ark_ed_on_bls12_381::fq::Fq;
-    use ark_ff::Field;
     use ark_std::test_rng;
-    use falcon_rust::{NTTPolynomial, Polynomial, MODULUS};
+    use falcon_rust::{power_of_q_field, NTTPolynomial, Polynomial};
     const REPEAT: usize = 100;
     #[test]
     fn test_ntt_mul_circuit() -> Result<(), PlonkError> {
@@ -194,9 +218,7 @@ mod tests {
         for _ in 0..REPEAT {
             let mut cs = PlonkCircuit::new_ultra_plonk(8);
             // the [q, 2*q^2, 4 * q^3, ..., 2^9 * q^10] constant wires
-            let const_power_q: Vec<Fq> = (1..LOG_N + 2)
-                .map(|x| Fq::from((1 << (x - 1)) as u64) * Fq::from(MODULUS).pow(&[x as u64]))
-                .collect();
+            let const_power_q: Vec<Fq> = power_of_q_field::<Fq>();
             let poly = Polynomial::rand(&mut rng);
             let poly_var = PolyVar::<Fq>::alloc_vars(&mut cs, &poly)?;
 
@@ -215,4 +237,40 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_alloc_vars_from_slice() -> Result<(), PlonkError> {
+        let mut rng = test_rng();
+        let mut cs = PlonkCircuit::<Fq>::new_ultra_plonk(8);
+        let poly = NTTPolynomial::rand(&mut rng);
+
+        let poly_var = NTTPolyVar::<Fq>::alloc_vars_from_slice(&mut cs, poly.coeff())?;
+        for (&var, &e) in poly_var.coeff().iter().zip(poly.coeff().iter()) {
+            assert_eq!(cs.witness(var)?, Fq::from(e));
+        }
+
+        let mut padded = poly.coeff().to_vec();
+        padded.push(0);
+        for coeff in [&poly.coeff()[..N - 1], &padded[..], &[]] {
+            let res = NTTPolyVar::<Fq>::alloc_vars_from_slice(&mut cs, coeff);
+            assert!(matches!(res, Err(PlonkError::InvalidParameters(_))));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_ntt_circuit_short_power_of_q() -> Result<(), PlonkError> {
+        let mut rng = test_rng();
+        let mut cs = PlonkCircuit::new_ultra_plonk(8);
+        let const_power_q: Vec<Fq> = power_of_q_field::<Fq>();
+        let poly = Polynomial::rand(&mut rng);
+        let poly_var = PolyVar::<Fq>::alloc_vars(&mut cs, &poly)?;
+
+        let res = NTTPolyVar::ntt_circuit_defer_mod_q(&mut cs, &poly_var, &const_power_q[..LOG_N]);
+        assert!(matches!(res, Err(PlonkError::InvalidParameters(_))));
+
+        let res = NTTPolyVar::ntt_circuit_full(&mut cs, &poly_var, &[]);
+        assert!(matches!(res, Err(PlonkError::InvalidParameters(_))));
+        Ok(())
+    }
 }
@@ -1,6 +1,6 @@
-use super::PolyVar;
+use super::{NTTPolyVar, PolyVar};
 use ark_ff::PrimeField;
-use falcon_rust::Polynomial;
+use falcon_rust::{power_of_q_field, Polynomial, PolynomialVar};
 use jf_plonk::{
     circuit::{Circuit, PlonkCircuit, Variable},
     errors::PlonkError,
@@ -63,3 +63,46 @@ impl<F: PrimeField> PolyVar<F> {
         Ok(Self::new(res))
     }
 }
+
+impl<F: PrimeField> PolynomialVar for PolyVar<F> {
+    type ConstraintSystem = PlonkCircuit<F>;
+    type Variable = Variable;
+    type NTTVar = NTTPolyVar<F>;
+    type Error = PlonkError;
+
+    fn alloc(cs: &mut PlonkCircuit<F>, poly: &Polynomial) -> Result<Self, PlonkError> {
+        Self::alloc_vars(cs, poly)
+    }
+
+    fn coeff(&self) -> &[Variable] {
+        &self.coeff
+    }
+
+    fn ntt(&self, cs: &mut PlonkCircuit<F>) -> Result<NTTPolyVar<F>, PlonkError> {
+        NTTPolyVar::ntt_circuit_full(cs, self, &power_of_q_field::<F>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ed_on_bls12_381::fq::Fq;
+    use ark_std::test_rng;
+    use falcon_rust::{alloc_and_ntt, NTTPolynomial, N};
+
+    #[test]
+    fn test_polynomial_var() -> Result<(), PlonkError> {
+        let mut rng = test_rng();
+        let mut cs = PlonkCircuit::<Fq>::new_ultra_plonk(8);
+        let poly = Polynomial::rand(&mut rng);
+        let output = NTTPolynomial::from(&poly);
+
+        let (poly_var, ntt_var) = alloc_and_ntt::<PolyVar<Fq>>(&mut cs, &poly)?;
+        for i in 0..N {
+            assert_eq!(Fq::from(poly.coeff()[i]), cs.witness(poly_var.coeff[i])?);
+            assert_eq!(Fq::from(output.coeff()[i]), cs.witness(ntt_var.coeff()[i])?);
+        }
+        assert!(cs.check_circuit_satisfiability(&[]).is_ok());
+        Ok(())
+    }
+}
@@ -1,5 +1,5 @@
 use ark_ff::PrimeField;
-use falcon_rust::N;
+use falcon_rust::{MODULUS, N, SIG_L2_BOUND};
 use jf_plonk::{
     circuit::{Circuit, PlonkCircuit, Variable},
     errors::PlonkError,
@@ -164,10 +164,23 @@ pub fn enforce_leq_765<F: PrimeField>(
 //     Ok(res)
 // }
 
+// The largest value l2_norm_var may compute: the sum of the squares
+// of 4N coefficients in [0, 12289), about 2^39.2 for N = 1024.
+const MAX_L2_NORM: u64 = 4 * N as u64 * (MODULUS as u64 - 1) * (MODULUS as u64 - 1);
+
+// Whether a field whose modulus has modulus_bits bits holds MAX_L2_NORM
+// without wrapping around; the modulus is at least 2^(modulus_bits - 1).
+fn field_holds_max_l2_norm(modulus_bits: usize) -> bool {
+    modulus_bits > (64 - MAX_L2_NORM.leading_zeros()) as usize
+}
+
 // compute the l2 norm of polynomial a where a's coefficients
 // are positive between [0, 12289).
 // This is applied over the `DualPolynomial` so we don't really
 // need to care for the signs.
+// Returns an error if the field is too small for the sum of squares
+// to be computed without wrapping around, which would make the norm
+// check unsound.
 pub fn l2_norm_var<F: PrimeField>(
     cs: &mut PlonkCircuit<F>,
     u: &DualPolyVar<F>,
@@ -176,6 +189,14 @@ pub fn l2_norm_var<F: PrimeField>(
     #[cfg(feature = "print-trace")]
     let cs_count = cs.num_gates();
 
+    if !field_holds_max_l2_norm(F::size_in_bits()) {
+        return Err(PlonkError::InvalidParameters(format!(
+            "field of {} bits is too small for a norm up to {}",
+            F::size_in_bits(),
+            MAX_L2_NORM
+        )));
+    }
+
     let mut t = vec![];
     for i in 0..N / 2 {
         let coeffs = [F::one(), F::one()];
@@ -253,34 +274,40 @@ pub fn l2_norm_var<F: PrimeField>(
     Ok(res)
 }
 
-// pub fn enforce_less_than_norm_bound<F: PrimeField>(
-//     cs: &mut PlonkCircuit<F>,
-//     a: &Variable,
-// ) -> Result<(), PlonkError> {
-//     #[cfg(feature = "falcon-512")]
-//     enforce_less_than_norm_bound_512(cs, a)?;
-//     #[cfg(feature = "falcon-1024")]
-//     enforce_less_than_norm_bound_1024(cs, a)?;
+/// Constraint that the witness of a is smaller than SIG_L2_BOUND,
+/// i.e., 34034726 for falcon-512 and 70265242 for falcon-1024.
+/// Cost: two 32 bits range gates and one lc gate.
+pub fn enforce_less_than_norm_bound<F: PrimeField>(
+    cs: &mut PlonkCircuit<F>,
+    a: &Variable,
+) -> Result<(), PlonkError> {
+    #[cfg(feature = "print-trace")]
+    let cs_count = cs.num_gates();
 
-//     Ok(())
-// }
+    // we prove that both a and b = SIG_L2_BOUND - 1 - a are 32 bits
+    // integers; since the field is a lot larger than 2^33, this
+    // implies a <= SIG_L2_BOUND - 1
+    let wires = [*a, cs.one(), cs.zero(), cs.zero()];
+    let coeffs = [-F::one(), F::from(SIG_L2_BOUND - 1), F::zero(), F::zero()];
+    let b = cs.lc(&wires, &coeffs)?;
 
-// /// Constraint that the witness of a is smaller than 34034726
-// /// Cost: XX constraints.
-// /// (This improves the range proof of 1264 constraints as in Arkworks.)
-// #[cfg(feature = "falcon-1024")]
-// fn enforce_less_than_norm_bound_1024<F: PrimeField>(
-//     cs: &mut PlonkCircuit<F>,
-//     a: &Variable,
-// ) -> Result<(), PlonkError> {
-//     // 34034726 = 2 * (2^8)^3 + 7 * (2^8)^2 + 84 * 2^8 + 38
+    cs.range_gate(*a, 32)?;
+    cs.range_gate(b, 32)?;
 
-//     Ok(())
-// }
+    #[cfg(feature = "print-trace")]
+    println!(
+        "enforce less than norm bound {};  total {}",
+        cs.num_gates() - cs_count,
+        cs.num_gates()
+    );
+
+    Ok(())
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::poly::PolyVar;
     use ark_ed_on_bls12_381::fq::Fq;
     use ark_ff::Zero;
     use ark_std::{rand::Rng, test_rng};
@@ -467,6 +494,128 @@ mod tests {
         Ok(())
     }
 
+    macro_rules! enforce_less_than_norm_bound {
+        ($value: expr, $satisfied: expr) => {
+            let mut cs = PlonkCircuit::new_ultra_plonk(8);
+            let a = Fq::from($value);
+            let a_var = cs.create_variable(a)?;
+
+            enforce_less_than_norm_bound(&mut cs, &a_var).unwrap();
+            if $satisfied {
+                assert!(cs.check_circuit_satisfiability(&[]).is_ok());
+            } else {
+                assert!(cs.check_circuit_satisfiability(&[]).is_err());
+            }
+        };
+    }
+    #[test]
+    fn test_enforce_less_than_norm_bound() -> Result<(), PlonkError> {
+        // =======================
+        // good path
+        // =======================
+        // the meaning of life
+        enforce_less_than_norm_bound!(42u64, true);
+
+        // edge case: 0
+        enforce_less_than_norm_bound!(0u64, true);
+
+        // edge case: 2^24
+        enforce_less_than_norm_bound!(1u64 << 24, true);
+
+        // edge case: 2^25
+        enforce_less_than_norm_bound!(1u64 << 25, true);
+
+        #[cfg(feature = "falcon-1024")]
+        // edge case: 2^26
+        enforce_less_than_norm_bound!(1u64 << 26, true);
+
+        // edge case: SIG_L2_BOUND - 1
+        enforce_less_than_norm_bound!(SIG_L2_BOUND - 1, true);
+
+        // =======================
+        // bad path
+        // =======================
+        // edge case: SIG_L2_BOUND
+        enforce_less_than_norm_bound!(SIG_L2_BOUND, false);
+
+        // edge case: SIG_L2_BOUND + 1
+        enforce_less_than_norm_bound!(SIG_L2_BOUND + 1, false);
+
+        #[cfg(feature = "falcon-512")]
+        // edge case: 2^26
+        enforce_less_than_norm_bound!(1u64 << 26, false);
+
+        // edge case: 2^27
+        enforce_less_than_norm_bound!(1u64 << 27, false);
+
+        // edge case: 2^32 - 1, the largest value passing the range gate on a
+        enforce_less_than_norm_bound!((1u64 << 32) - 1, false);
+
+        // edge case: 2^32
+        enforce_less_than_norm_bound!(1u64 << 32, false);
+
+        // edge case: -1
+        enforce_less_than_norm_bound!(-Fq::from(1u64), false);
+
+        // =======================
+        // random path
+        // =======================
+        let mut rng = test_rng();
+        for _ in 0..REPEAT {
+            let t = rng.gen_range(0..1 << 28) as u64;
+            enforce_less_than_norm_bound!(t, t < SIG_L2_BOUND);
+        }
+        // values around the bound
+        for _ in 0..REPEAT {
+            let t = SIG_L2_BOUND - 1000 + rng.gen_range(0..2000) as u64;
+            enforce_less_than_norm_bound!(t, t < SIG_L2_BOUND);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_infinity_norm_vs_l2_norm() -> Result<(), PlonkError> {
+        // dual polynomials with every coefficient at 765 pass the
+        // infinity norm check, but their l2 norm 4N * 765^2 is way over
+        // the l2 norm bound
+        let mut cs = PlonkCircuit::<Fq>::new_ultra_plonk(8);
+        let mut vars = vec![];
+        for _ in 0..4 {
+            let coeff = (0..N)
+                .map(|_| cs.create_variable(Fq::from(765u64)))
+                .collect::<Result<Vec<_>, _>>()?;
+            vars.push(PolyVar::new(coeff));
+        }
+        let u_var = DualPolyVar {
+            pos: vars[0].clone(),
+            neg: vars[1].clone(),
+        };
+        let v_var = DualPolyVar {
+            pos: vars[2].clone(),
+            neg: vars[3].clone(),
+        };
+
+        for e in u_var
+            .pos
+            .coeff
+            .iter()
+            .chain(u_var.neg.coeff.iter())
+            .chain(v_var.pos.coeff.iter())
+            .chain(v_var.neg.coeff.iter())
+        {
+            enforce_leq_765(&mut cs, e)?;
+        }
+        assert!(cs.check_circuit_satisfiability(&[]).is_ok());
+        assert!(4 * N as u64 * 765 * 765 >= SIG_L2_BOUND);
+
+        let norm_var = l2_norm_var(&mut cs, &u_var, &v_var)?;
+        assert_eq!(cs.witness(norm_var)?, Fq::from(4 * N as u64 * 765 * 765));
+        enforce_less_than_norm_bound(&mut cs, &norm_var)?;
+        assert!(cs.check_circuit_satisfiability(&[]).is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn test_l2_norm() -> Result<(), PlonkError> {
         let mut rng = test_rng();
@@ -508,4 +657,17 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_field_holds_max_l2_norm() {
+        assert!(MAX_L2_NORM < 1 << 40);
+        assert!(field_holds_max_l2_norm(Fq::size_in_bits()));
+        assert!(field_holds_max_l2_norm(64));
+
+        // just short of MAX_L2_NORM
+        let max_norm_bits = (64 - MAX_L2_NORM.leading_zeros()) as usize;
+        assert!(!field_holds_max_l2_norm(max_norm_bits));
+        assert!(!field_holds_max_l2_norm(32));
+        assert!(field_holds_max_l2_norm(max_norm_bits + 1));
+    }
 }
@@ -0,0 +1,64 @@
+#[macro_use]
+extern crate criterion;
+
+use ark_ed_on_bls12_381::fq::Fq;
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
+use criterion::Criterion;
+use falcon_r1cs::{
+    FalconDualNTTVerificationCircuit, FalconNTTVerificationCircuit,
+    FalconSchoolBookVerificationCircuit,
+};
+use falcon_rust::KeyPair;
+
+criterion_main!(bench);
+criterion_group!(bench, bench_circuit_synthesis,);
+
+fn bench_circuit_synthesis(c: &mut Criterion) {
+    let mut bench_group = c.benchmark_group("Constraint synthesis");
+    bench_group.sample_size(10);
+
+    let keypair = KeyPair::keygen_with_seed("bench seed".as_ref());
+    let message = "testing message".as_bytes();
+    let sig = keypair
+        .secret_key
+        .sign_with_seed("test seed".as_ref(), message.as_ref());
+
+    {
+        let circuit =
+            FalconNTTVerificationCircuit::build_circuit(keypair.public_key, message.to_vec(), sig);
+        bench_group.bench_function("verify with ntt", move |b| {
+            b.iter(|| {
+                let cs = ConstraintSystem::<Fq>::new_ref();
+                circuit.clone().generate_constraints(cs).unwrap();
+            });
+        });
+    }
+
+    {
+        let circuit = FalconDualNTTVerificationCircuit::build_circuit(
+            keypair.public_key,
+            message.to_vec(),
+            sig,
+        );
+        bench_group.bench_function("verify with dual ntt", move |b| {
+            b.iter(|| {
+                let cs = ConstraintSystem::<Fq>::new_ref();
+                circuit.clone().generate_constraints(cs).unwrap();
+            });
+        });
+    }
+
+    {
+        let circuit = FalconSchoolBookVerificationCircuit::build_circuit(
+            keypair.public_key,
+            message.to_vec(),
+            sig,
+        );
+        bench_group.bench_function("verify with schoolbook", move |b| {
+            b.iter(|| {
+                let cs = ConstraintSystem::<Fq>::new_ref();
+                circuit.clone().generate_constraints(cs).unwrap();
+            });
+        });
+    }
+}
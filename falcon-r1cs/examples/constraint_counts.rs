@@ -4,7 +4,7 @@ use ark_r1cs_std::{
     fields::fp::FpVar,
     R1CSVar,
 };
-use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem, Field};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
 use ark_std::test_rng;
 use falcon_r1cs::*;
 use falcon_rust::*;
@@ -12,6 +12,7 @@ use falcon_rust::*;
 fn main() {
     println!("                  # instance variables |      # witness |      #constraints |");
     count_ntt_conversion_constraints();
+    count_ntt_conversion_breakdown();
     count_verify_with_ntt_constraints();
     count_verify_with_dual_ntt_constraints();
     count_verify_with_schoolbook_constraints();
@@ -24,6 +25,7 @@ fn count_verify_with_schoolbook_constraints() {
         .secret_key
         .sign_with_seed("test seed".as_ref(), message.as_ref());
 
+    #[cfg(feature = "ffi")]
     assert!(keypair.public_key.verify(message.as_ref(), &sig));
     assert!(keypair.public_key.verify_rust(message.as_ref(), &sig));
 
@@ -53,6 +55,7 @@ fn count_verify_with_ntt_constraints() {
         .secret_key
         .sign_with_seed("test seed".as_ref(), message.as_ref());
 
+    #[cfg(feature = "ffi")]
     assert!(keypair.public_key.verify(message.as_ref(), &sig));
     assert!(keypair.public_key.verify_rust(message.as_ref(), &sig));
 
@@ -80,14 +83,9 @@ fn count_ntt_conversion_constraints() {
     let poly_var = PolyVar::<Fq>::alloc_vars(cs.clone(), &poly, AllocationMode::Witness).unwrap();
 
     // the [q, 2*q^2, 4 * q^3, ..., 2^9 * q^10] constant wires
-    let const_mod_q_vars: Vec<FpVar<Fq>> = (1..LOG_N + 2)
-        .map(|x| {
-            FpVar::<Fq>::new_constant(
-                cs.clone(),
-                Fq::from(1 << (x - 1)) * Fq::from(MODULUS).pow(&[x as u64]),
-            )
-            .unwrap()
-        })
+    let const_mod_q_vars: Vec<FpVar<Fq>> = power_of_q_field::<Fq>()
+        .iter()
+        .map(|x| FpVar::<Fq>::new_constant(cs.clone(), x).unwrap())
         .collect();
     let output = NTTPolynomial::from(&poly);
 
@@ -112,6 +110,58 @@ fn count_ntt_conversion_constraints() {
     }
 }
 
+// split the cost of the NTT conversion between the native butterflies
+// and the final mod q reduction of the outputs
+fn count_ntt_conversion_breakdown() {
+    let mut rng = test_rng();
+
+    let cs = ConstraintSystem::<Fq>::new_ref();
+    let param_var = ntt_param_var(cs.clone()).unwrap();
+    let poly = Polynomial::rand(&mut rng);
+    let poly_var = PolyVar::<Fq>::alloc_vars(cs.clone(), &poly, AllocationMode::Witness).unwrap();
+    let const_mod_q_vars: Vec<FpVar<Fq>> = power_of_q_field::<Fq>()
+        .iter()
+        .map(|x| FpVar::<Fq>::new_constant(cs.clone(), x).unwrap())
+        .collect();
+    let output = NTTPolynomial::from(&poly);
+
+    let num_instance_variables = cs.num_instance_variables();
+    let num_witness_variables = cs.num_witness_variables();
+    let num_constraints = cs.num_constraints();
+
+    let mut output_var =
+        NTTPolyVar::ntt_circuit_defer_range_check(&poly_var, &const_mod_q_vars, &param_var)
+            .unwrap();
+    println!(
+        "  ntt butterflies:            {:8} |       {:8} |          {:8} |",
+        cs.num_instance_variables() - num_instance_variables,
+        cs.num_witness_variables() - num_witness_variables,
+        cs.num_constraints() - num_constraints,
+    );
+
+    let num_instance_variables = cs.num_instance_variables();
+    let num_witness_variables = cs.num_witness_variables();
+    let num_constraints = cs.num_constraints();
+
+    for e in output_var.0.iter_mut() {
+        *e = mod_q(cs.clone(), e, &const_mod_q_vars[0]).unwrap();
+    }
+    println!(
+        "  ntt mod q reduction:        {:8} |       {:8} |          {:8} |",
+        cs.num_instance_variables() - num_instance_variables,
+        cs.num_witness_variables() - num_witness_variables,
+        cs.num_constraints() - num_constraints,
+    );
+
+    for i in 0..N {
+        assert_eq!(
+            Fq::from(output.coeff()[i]),
+            output_var.coeff()[i].value().unwrap()
+        )
+    }
+    assert!(cs.is_satisfied().unwrap());
+}
+
 fn count_verify_with_dual_ntt_constraints() {
     let keypair = KeyPair::keygen();
     let message = "testing message".as_bytes();
@@ -119,6 +169,7 @@ fn count_verify_with_dual_ntt_constraints() {
         .secret_key
         .sign_with_seed("test seed".as_ref(), message.as_ref());
 
+    #[cfg(feature = "ffi")]
     assert!(keypair.public_key.verify(message.as_ref(), &sig));
     assert!(keypair.public_key.verify_rust(message.as_ref(), &sig));
 
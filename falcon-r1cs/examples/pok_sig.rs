@@ -4,8 +4,8 @@ use ark_bls12_381::{Bls12_381, Fr};
 use ark_groth16::{create_random_proof, verify_proof, Groth16, PreparedVerifyingKey};
 use ark_snark::SNARK;
 use ark_std::rand::SeedableRng;
-use falcon_r1cs::FalconNTTVerificationCircuit;
-use falcon_rust::{KeyPair, NTTPolynomial, Polynomial};
+use falcon_r1cs::{FalconNTTVerificationCircuit, FalconPublicInputs};
+use falcon_rust::KeyPair;
 use rand_chacha::ChaCha20Rng;
 
 fn main() {
@@ -18,6 +18,7 @@ fn main() {
     let sig = keypair
         .secret_key
         .sign_with_seed("test seed".as_ref(), msg.as_ref());
+    #[cfg(feature = "ffi")]
     assert!(keypair.public_key.verify(msg.as_ref(), &sig));
 
     // build the circuit
@@ -30,18 +31,8 @@ fn main() {
     let (pp, vk) =
         Groth16::<Bls12_381>::circuit_specific_setup(cs_input.clone(), &mut rng).unwrap();
     let proof = create_random_proof(cs_input, &pp, &mut rng).unwrap();
-    let pk = Polynomial::from(&(keypair.public_key));
-    let pk_ntt = NTTPolynomial::from(&pk);
-    let hm = Polynomial::from_hash_of_message(msg.as_ref(), sig.nonce());
-    let hm_ntt = NTTPolynomial::from(&hm);
-
-    let mut public_inputs = Vec::new();
-    for e in pk_ntt.coeff() {
-        public_inputs.push(Fr::from(*e))
-    }
-    for e in hm_ntt.coeff() {
-        public_inputs.push(Fr::from(*e))
-    }
+    let public_inputs = FalconPublicInputs::new(&keypair.public_key, msg.as_ref(), sig.nonce())
+        .to_field_elements::<Fr>();
     let pvk = PreparedVerifyingKey::from(vk.clone());
 
     assert!(verify_proof(&pvk, &proof, &public_inputs).unwrap())
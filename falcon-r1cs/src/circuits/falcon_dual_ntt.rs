@@ -1,6 +1,6 @@
 use crate::gadgets::*;
 use ark_ff::PrimeField;
-use ark_r1cs_std::{alloc::AllocVar, fields::fp::FpVar, prelude::*};
+use ark_r1cs_std::prelude::*;
 use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, Result};
 use falcon_rust::*;
 
@@ -15,6 +15,18 @@ impl FalconDualNTTVerificationCircuit {
     pub fn build_circuit(pk: PublicKey, msg: Vec<u8>, sig: Signature) -> Self {
         Self { pk, msg, sig }
     }
+
+    /// The public input blocks; the same as for
+    /// `FalconNTTVerificationCircuit`
+    pub fn public_input_layout() -> Vec<(&'static str, usize)> {
+        vec![("pk_ntt", N), ("hm_ntt", N)]
+    }
+
+    /// The number of public field elements; the same as for
+    /// `FalconNTTVerificationCircuit`
+    pub fn num_public_inputs() -> usize {
+        Self::public_input_layout().iter().map(|(_, n)| n).sum()
+    }
 }
 
 impl<F: PrimeField> ConstraintSynthesizer<F> for FalconDualNTTVerificationCircuit {
@@ -28,16 +40,9 @@ impl<F: PrimeField> ConstraintSynthesizer<F> for FalconDualNTTVerificationCircui
         let pk_poly: Polynomial = (&self.pk).into();
 
         // the [q, 2*q^2, 4 * q^3, ..., 2^9 * q^10] constant wires
-        let const_q_power_vars: Vec<FpVar<F>> = (1..LOG_N + 2)
-            .map(|x| {
-                FpVar::<F>::new_constant(
-                    cs.clone(),
-                    F::from(1u32 << (x - 1)) * F::from(MODULUS).pow(&[x as u64]),
-                )
-                .unwrap()
-            })
-            .collect();
-        let param_vars = ntt_param_var(cs.clone()).unwrap();
+        // and the NTT table
+        let mut ntt_context = NttContext::new(cs.clone())?;
+        let (const_q_power_vars, param_vars) = ntt_context.wires(cs.clone())?;
         // ========================================
         // compute related data in the clear
         // ========================================
@@ -85,11 +90,11 @@ impl<F: PrimeField> ConstraintSynthesizer<F> for FalconDualNTTVerificationCircui
         let sig_ntt_vars = DualNTTPolyVar::ntt_circuit(
             cs.clone(),
             &sig_poly_vars,
-            &const_q_power_vars,
-            &param_vars,
+            const_q_power_vars,
+            param_vars,
         )?;
         let v_ntt_vars =
-            DualNTTPolyVar::ntt_circuit(cs.clone(), &v_vars, &const_q_power_vars, &param_vars)?;
+            DualNTTPolyVar::ntt_circuit(cs.clone(), &v_vars, const_q_power_vars, param_vars)?;
 
         // second, prove the equation holds in the ntt domain
         for i in 0..N {
@@ -137,7 +142,7 @@ mod tests {
 
     use super::*;
     use ark_ed_on_bls12_381::fq::Fq;
-    use ark_relations::r1cs::ConstraintSystem;
+    use ark_relations::r1cs::{ConstraintSystem, SynthesisError};
     #[test]
     fn test_ntt_verification_r1cs() {
         let keypair = KeyPair::keygen();
@@ -146,6 +151,7 @@ mod tests {
             .secret_key
             .sign_with_seed("test seed".as_ref(), message.as_ref());
 
+        #[cfg(feature = "ffi")]
         assert!(keypair.public_key.verify(message.as_ref(), &sig));
         assert!(keypair.public_key.verify_rust(message.as_ref(), &sig));
 
@@ -166,5 +172,40 @@ mod tests {
         // );
 
         assert!(cs.is_satisfied().unwrap());
+
+        let layout = FalconDualNTTVerificationCircuit::public_input_layout();
+        assert_eq!(
+            layout.iter().map(|(_, len)| len).sum::<usize>(),
+            cs.num_instance_variables() - 1
+        );
+        assert_eq!(
+            FalconDualNTTVerificationCircuit::num_public_inputs(),
+            cs.num_instance_variables() - 1
+        );
+    }
+
+    #[test]
+    fn test_synthesis_error_propagation() {
+        let keypair = KeyPair::keygen();
+        let message = "testing message".as_bytes();
+        let sig = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), message.as_ref());
+
+        // without a constraint system, ntt_param_var fails before any
+        // witness is allocated; the error has to be returned, where
+        // it used to be unwrapped
+        let cs = ConstraintSystemRef::<Fq>::None;
+
+        let falcon_circuit = FalconDualNTTVerificationCircuit::build_circuit(
+            keypair.public_key,
+            message.to_vec(),
+            sig,
+        );
+
+        match falcon_circuit.generate_constraints(cs) {
+            Err(SynthesisError::MissingCS) => (),
+            res => panic!("unexpected result: {:?}", res),
+        }
     }
 }
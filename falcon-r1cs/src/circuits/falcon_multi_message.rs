@@ -0,0 +1,170 @@
+use super::falcon_ntt::enforce_ntt_verification;
+use crate::gadgets::*;
+use ark_ff::PrimeField;
+use ark_r1cs_std::prelude::*;
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, Result};
+use falcon_rust::*;
+
+/// Verification of several signatures under a single public key.
+/// The public key is allocated once, and shared by all the messages.
+#[derive(Clone, Debug)]
+pub struct FalconMultiMessageCircuit {
+    pk: PublicKey,
+    msgs_and_sigs: Vec<(Vec<u8>, Signature)>,
+}
+
+impl FalconMultiMessageCircuit {
+    pub fn build_circuit(pk: PublicKey, msgs_and_sigs: Vec<(Vec<u8>, Signature)>) -> Self {
+        Self { pk, msgs_and_sigs }
+    }
+
+    /// The number of public field elements for `num_msgs` messages:
+    /// pk_ntt, followed by hm_ntt for each message. The constant one
+    /// is not included.
+    pub fn num_public_inputs(num_msgs: usize) -> usize {
+        (1 + num_msgs) * N
+    }
+}
+
+impl<F: PrimeField> ConstraintSynthesizer<F> for FalconMultiMessageCircuit {
+    /// generate a circuit proving that for a given pk, and for each
+    /// (msg, sig) tuple the following statement holds
+    /// - hm = hash_message(message, nonce)     <- done in public
+    /// - v = hm - sig * pk
+    /// - l2_norm(sig, v) < SIG_L2_BOUND
+    ///
+    /// The public inputs are pk_ntt, followed by hm_ntt for each message.
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> Result<()> {
+        let pk_poly: Polynomial = (&self.pk).into();
+        let pk_ntt = NTTPolynomial::from(&pk_poly);
+
+        // the [q, 2*q^2, 4 * q^3, ..., 2^9 * q^10] constant wires
+        // and the NTT table
+        let mut ntt_context = NttContext::new(cs.clone())?;
+        let (const_q_power_vars, param_vars) = ntt_context.wires(cs.clone())?;
+
+        // pk, in NTT domain
+        //  a public input to the circuit, shared by all the messages;
+        //  do not need range proof
+        let pk_ntt_vars = NTTPolyVar::<F>::alloc_vars(cs.clone(), &pk_ntt, AllocationMode::Input)?;
+
+        for (msg, sig) in self.msgs_and_sigs.iter() {
+            let hm = Polynomial::from_hash_of_message(msg.as_ref(), sig.nonce());
+            enforce_ntt_verification(
+                cs.clone(),
+                &pk_poly,
+                &pk_ntt_vars,
+                &hm,
+                &sig.into(),
+                const_q_power_vars,
+                param_vars,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FalconNTTVerificationCircuit;
+    use ark_ed_on_bls12_381::fq::Fq;
+    use ark_relations::r1cs::ConstraintSystem;
+
+    #[test]
+    fn test_multi_message_r1cs() {
+        let keypair = KeyPair::keygen();
+        let num_msgs = 3;
+        let msgs_and_sigs: Vec<(Vec<u8>, Signature)> = (0..num_msgs)
+            .map(|i| {
+                let msg = format!("testing message {}", i).into_bytes();
+                let sig = keypair
+                    .secret_key
+                    .sign_with_seed("test seed".as_ref(), msg.as_ref());
+                (msg, sig)
+            })
+            .collect();
+
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let falcon_circuit =
+            FalconMultiMessageCircuit::build_circuit(keypair.public_key, msgs_and_sigs.clone());
+        falcon_circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+        assert_eq!(
+            FalconMultiMessageCircuit::num_public_inputs(num_msgs),
+            cs.num_instance_variables() - 1
+        );
+
+        // compare with the same messages verified in separate circuits
+        let mut num_instance_variables = 0;
+        let mut num_constraints = 0;
+        for (msg, sig) in msgs_and_sigs.iter() {
+            let cs = ConstraintSystem::<Fq>::new_ref();
+            let falcon_circuit =
+                FalconNTTVerificationCircuit::build_circuit(keypair.public_key, msg.clone(), *sig);
+            falcon_circuit.generate_constraints(cs.clone()).unwrap();
+            num_instance_variables += cs.num_instance_variables();
+            num_constraints += cs.num_constraints();
+        }
+        // the constant one and pk_ntt are shared
+        assert_eq!(
+            cs.num_instance_variables(),
+            num_instance_variables - (num_msgs - 1) * (N + 1)
+        );
+        // the constant wires do not cost constraints, so this is the same
+        assert_eq!(cs.num_constraints(), num_constraints);
+
+        // a wrong message
+        let mut bad_msgs_and_sigs = msgs_and_sigs;
+        bad_msgs_and_sigs[1].0 = "another testing message".as_bytes().to_vec();
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let falcon_circuit =
+            FalconMultiMessageCircuit::build_circuit(keypair.public_key, bad_msgs_and_sigs);
+        falcon_circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_multi_message_cost() {
+        let keypair = KeyPair::keygen_with_seed("test seed".as_ref());
+        let msgs_and_sigs: Vec<(Vec<u8>, Signature)> = (0..3)
+            .map(|i| {
+                let msg = format!("testing message {}", i).into_bytes();
+                let sig = keypair
+                    .secret_key
+                    .sign_with_seed("test seed".as_ref(), msg.as_ref());
+                (msg, sig)
+            })
+            .collect();
+
+        // a single message circuit
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let (msg, sig) = &msgs_and_sigs[0];
+        FalconNTTVerificationCircuit::build_circuit(keypair.public_key, msg.clone(), *sig)
+            .generate_constraints(cs.clone())
+            .unwrap();
+        let single_num_constraints = cs.num_constraints();
+        let single_num_instance_variables = cs.num_instance_variables();
+
+        for k in 1..=msgs_and_sigs.len() {
+            let cs = ConstraintSystem::<Fq>::new_ref();
+            FalconMultiMessageCircuit::build_circuit(
+                keypair.public_key,
+                msgs_and_sigs[..k].to_vec(),
+            )
+            .generate_constraints(cs.clone())
+            .unwrap();
+            assert!(cs.is_satisfied().unwrap());
+
+            // against k single message circuits: every extra message
+            // saves the constant one and pk_ntt, N + 1 instance
+            // variables, and no constraint
+            assert_eq!(
+                cs.num_instance_variables(),
+                k * single_num_instance_variables - (k - 1) * (N + 1)
+            );
+            assert_eq!(cs.num_constraints(), k * single_num_constraints);
+        }
+    }
+}
@@ -1,19 +1,61 @@
 use crate::gadgets::*;
 use ark_ff::PrimeField;
-use ark_r1cs_std::{alloc::AllocVar, fields::fp::FpVar, prelude::*};
+use ark_r1cs_std::{fields::fp::FpVar, prelude::*};
 use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, Result};
 use falcon_rust::*;
 
 #[derive(Clone, Debug)]
 pub struct FalconNTTVerificationCircuit {
     pk: PublicKey,
-    msg: Vec<u8>,
-    sig: Signature,
+    hm: Polynomial,
+    sig: Polynomial,
 }
 
 impl FalconNTTVerificationCircuit {
     pub fn build_circuit(pk: PublicKey, msg: Vec<u8>, sig: Signature) -> Self {
-        Self { pk, msg, sig }
+        let hm = Polynomial::from_hash_of_message(msg.as_ref(), sig.nonce());
+        Self::build_circuit_from_polys(pk, hm, (&sig).into())
+    }
+
+    /// Build the circuit from the hash of the message and the
+    /// signature in their polynomial forms, rather than from
+    /// the message and the encoded signature.
+    pub fn build_circuit_from_polys(pk: PublicKey, hm: Polynomial, sig: Polynomial) -> Self {
+        Self { pk, hm, sig }
+    }
+
+    /// The public inputs of the circuit, in allocation order:
+    /// the name and the number of field elements of each block.
+    /// The constant one is not included.
+    pub fn public_input_layout() -> Vec<(&'static str, usize)> {
+        vec![("pk_ntt", N), ("hm_ntt", N)]
+    }
+
+    /// The number of public field elements a verifier expects: the
+    /// total length of the layout. The constant one is not included.
+    pub fn num_public_inputs() -> usize {
+        Self::public_input_layout().iter().map(|(_, n)| n).sum()
+    }
+
+    /// An estimation of the number of constraints of the circuit,
+    /// derived from the documented costs of the gadgets, without
+    /// synthesizing it. Matches the actual count exactly.
+    pub fn estimated_constraints() -> usize {
+        // per coefficient of v and sig
+        //  - range proof of v: 29
+        //  - two NTT conversions: 2 * 30
+        //  - hm = v + sig * pk mod q: 1 multiplication, 30 for add_mod
+        //    and 1 equality
+        let per_coeff = 29 + 2 * 30 + 1 + 30 + 1;
+        // per coefficient of (v | sig) in the l2 norm:
+        //  17 for the lifting, 1 for the selection and 1 for the square
+        let per_norm_coeff = 17 + 1 + 1;
+        #[cfg(feature = "falcon-512")]
+        let norm_bound = 52;
+        #[cfg(not(feature = "falcon-512"))]
+        let norm_bound = 54;
+
+        N * per_coeff + 2 * N * per_norm_coeff + norm_bound
     }
 }
 
@@ -24,103 +66,111 @@ impl<F: PrimeField> ConstraintSynthesizer<F> for FalconNTTVerificationCircuit {
     /// - v = hm - sig * pk
     /// - l2_norm(sig, v) < SIG_L2_BOUND = 34034726
     fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> Result<()> {
-        let sig_poly: Polynomial = (&self.sig).into();
         let pk_poly: Polynomial = (&self.pk).into();
-
-        // the [q, 2*q^2, 4 * q^3, ..., 2^9 * q^10] constant wires
-        let const_q_power_vars: Vec<FpVar<F>> = (1..LOG_N + 2)
-            .map(|x| {
-                FpVar::<F>::new_constant(
-                    cs.clone(),
-                    F::from(1u32 << (x - 1)) * F::from(MODULUS).pow(&[x as u64]),
-                )
-                .unwrap()
-            })
-            .collect();
-        let param_vars = ntt_param_var(cs.clone()).unwrap();
-        // ========================================
-        // compute related data in the clear
-        // ========================================
-        let hm = Polynomial::from_hash_of_message(self.msg.as_ref(), self.sig.nonce());
-        let hm_ntt = NTTPolynomial::from(&hm);
-
-        // compute v = hm - uh and lift it to positives
-        let uh = sig_poly * pk_poly;
-        let v = hm - uh;
-
         let pk_ntt = NTTPolynomial::from(&pk_poly);
 
-        // ========================================
-        // allocate the variables with range checks
-        // ========================================
-        // signature, over Z
-        //  a private input to the circuit; a range proof will be done later
-        let sig_poly_vars =
-            PolyVar::<F>::alloc_vars(cs.clone(), &sig_poly, AllocationMode::Witness)?;
+        // the [q, 2*q^2, 4 * q^3, ..., 2^9 * q^10] constant wires
+        // and the NTT table
+        let mut ntt_context = NttContext::new(cs.clone())?;
+        let (const_q_power_vars, param_vars) = ntt_context.wires(cs.clone())?;
 
         // pk, in NTT domain
         //  a public input to the circuit; do not need range proof
         let pk_ntt_vars = NTTPolyVar::<F>::alloc_vars(cs.clone(), &pk_ntt, AllocationMode::Input)?;
 
-        // hash of message, in NTT domain
-        //  also a public input; do not need range proof
-        let hm_ntt_vars = NTTPolyVar::<F>::alloc_vars(cs.clone(), &hm_ntt, AllocationMode::Input)?;
+        enforce_ntt_verification(
+            cs,
+            &pk_poly,
+            &pk_ntt_vars,
+            &self.hm,
+            &self.sig,
+            const_q_power_vars,
+            param_vars,
+        )
+    }
+}
 
-        // v := hm - sig * pk, over Z
-        //  a private input to the circuit; require a range proof
-        let v_vars = PolyVar::<F>::alloc_vars(cs.clone(), &v, AllocationMode::Witness)?;
+/// The verification of a single signature, for a public key that is
+/// already allocated in NTT domain: hm_ntt is allocated as a public
+/// input, sig and v = hm - sig * pk as witnesses, and
+/// - v = hm - sig * pk mod MODULUS
+/// - l2_norm(sig, v) < SIG_L2_BOUND
+///
+/// are enforced. Shared by `FalconNTTVerificationCircuit` and
+/// `FalconMultiMessageCircuit`.
+pub(crate) fn enforce_ntt_verification<F: PrimeField>(
+    cs: ConstraintSystemRef<F>,
+    pk_poly: &Polynomial,
+    pk_ntt_vars: &NTTPolyVar<F>,
+    hm: &Polynomial,
+    sig_poly: &Polynomial,
+    const_q_power_vars: &[FpVar<F>],
+    param_vars: &[FpVar<F>],
+) -> Result<()> {
+    // ========================================
+    // compute related data in the clear
+    // ========================================
+    let hm_ntt = NTTPolynomial::from(hm);
 
-        for e in v_vars.coeff() {
-            // ensure all the v inputs are smaller than MODULUS
-            // v will need to be kept secret
-            enforce_less_than_q(cs.clone(), &e)?;
-        }
-        // ========================================
-        // proving v = hm + sig * pk mod MODULUS
-        // ========================================
-        // we are proving the polynomial congruence via NTT.
-        //
-
-        // first, prove that the circuit variable are indeed the
-        // NTT representation of the polynomial
-        //  sig_ntt_vars = ntt_circuit(sig_vars)
-        //  v_ntt_vars = ntt_circuit(v_vars)
-        let sig_ntt_vars =
-            NTTPolyVar::ntt_circuit(cs.clone(), &sig_poly_vars, &const_q_power_vars, &param_vars)?;
-        let v_ntt_vars =
-            NTTPolyVar::ntt_circuit(cs.clone(), &v_vars, &const_q_power_vars, &param_vars)?;
-
-        // second, prove the equation holds in the ntt domain
-        for i in 0..N {
-            // hm[i] = v[i] + sig[i] * pk[i] % MODULUS
-
-            // println!(
-            //     "{:?} {:?} {:?} {:?}",
-            //     v_ntt_vars[i].value()?.into_repr(),
-            //     hm_ntt_vars[i].value()?.into_repr(),
-            //     sig_ntt_vars[i].value()?.into_repr(),
-            //     pk_ntt_vars[i].value()?.into_repr(),
-            // );
-
-            hm_ntt_vars.coeff()[i].enforce_equal(&add_mod(
-                cs.clone(),
-                &v_ntt_vars.coeff()[i],
-                &(&sig_ntt_vars.coeff()[i] * &pk_ntt_vars.coeff()[i]),
-                &const_q_power_vars[0],
-            )?)?;
-        }
+    // compute v = hm - uh and lift it to positives
+    let uh = *sig_poly * *pk_poly;
+    let v = *hm - uh;
+
+    // ========================================
+    // allocate the variables with range checks
+    // ========================================
+    // signature, over Z
+    //  a private input to the circuit; a range proof will be done later
+    let sig_poly_vars = PolyVar::<F>::alloc_vars(cs.clone(), sig_poly, AllocationMode::Witness)?;
+
+    // hash of message, in NTT domain
+    //  a public input; do not need range proof
+    let hm_ntt_vars = NTTPolyVar::<F>::alloc_vars(cs.clone(), &hm_ntt, AllocationMode::Input)?;
+
+    // v := hm - sig * pk, over Z
+    //  a private input to the circuit; require a range proof
+    let v_vars = PolyVar::<F>::alloc_vars(cs.clone(), &v, AllocationMode::Witness)?;
 
-        // ========================================
-        // proving l2_norm(v | sig) < 34034726
-        // ========================================
-        let l2_norm_var = l2_norm_var(
+    for e in v_vars.coeff() {
+        // ensure all the v inputs are smaller than MODULUS
+        // v will need to be kept secret
+        enforce_less_than_q(cs.clone(), e)?;
+    }
+    // ========================================
+    // proving v = hm + sig * pk mod MODULUS
+    // ========================================
+    // we are proving the polynomial congruence via NTT.
+    //
+
+    // first, prove that the circuit variable are indeed the
+    // NTT representation of the polynomial
+    //  sig_ntt_vars = ntt_circuit(sig_vars)
+    //  v_ntt_vars = ntt_circuit(v_vars)
+    let sig_ntt_vars =
+        NTTPolyVar::ntt_circuit(cs.clone(), &sig_poly_vars, const_q_power_vars, param_vars)?;
+    let v_ntt_vars = NTTPolyVar::ntt_circuit(cs.clone(), &v_vars, const_q_power_vars, param_vars)?;
+
+    // second, prove the equation holds in the ntt domain
+    for i in 0..N {
+        // hm[i] = v[i] + sig[i] * pk[i] % MODULUS
+        hm_ntt_vars.coeff()[i].enforce_equal(&add_mod(
             cs.clone(),
-            &[v_vars.coeff(), sig_poly_vars.coeff()].concat(),
+            &v_ntt_vars.coeff()[i],
+            &(&sig_ntt_vars.coeff()[i] * &pk_ntt_vars.coeff()[i]),
             &const_q_power_vars[0],
-        )?;
-
-        enforce_less_than_norm_bound(cs, &l2_norm_var)
+        )?)?;
     }
+
+    // ========================================
+    // proving l2_norm(v | sig) < SIG_L2_BOUND
+    // ========================================
+    let l2_norm_var = l2_norm_var(
+        cs.clone(),
+        &[v_vars.coeff(), sig_poly_vars.coeff()].concat(),
+        &const_q_power_vars[0],
+    )?;
+
+    enforce_less_than_norm_bound(cs, &l2_norm_var)
 }
 
 #[cfg(test)]
@@ -128,7 +178,9 @@ mod tests {
 
     use super::*;
     use ark_ed_on_bls12_381::fq::Fq;
-    use ark_relations::r1cs::ConstraintSystem;
+    use ark_relations::r1cs::{ConstraintSystem, SynthesisError};
+    use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+
     #[test]
     fn test_ntt_verification_r1cs() {
         let keypair = KeyPair::keygen();
@@ -137,16 +189,14 @@ mod tests {
             .secret_key
             .sign_with_seed("test seed".as_ref(), message.as_ref());
 
+        #[cfg(feature = "ffi")]
         assert!(keypair.public_key.verify(message.as_ref(), &sig));
         assert!(keypair.public_key.verify_rust(message.as_ref(), &sig));
 
         let cs = ConstraintSystem::<Fq>::new_ref();
 
-        let falcon_circuit = FalconNTTVerificationCircuit {
-            pk: keypair.public_key,
-            msg: message.to_vec(),
-            sig,
-        };
+        let falcon_circuit =
+            FalconNTTVerificationCircuit::build_circuit(keypair.public_key, message.to_vec(), sig);
 
         falcon_circuit.generate_constraints(cs.clone()).unwrap();
         // println!(
@@ -157,5 +207,161 @@ mod tests {
         // );
 
         assert!(cs.is_satisfied().unwrap());
+
+        let layout = FalconNTTVerificationCircuit::public_input_layout();
+        assert_eq!(
+            layout.iter().map(|(_, len)| len).sum::<usize>(),
+            cs.num_instance_variables() - 1
+        );
+        assert_eq!(
+            FalconNTTVerificationCircuit::num_public_inputs(),
+            cs.num_instance_variables() - 1
+        );
+    }
+
+    #[cfg(feature = "falcon-512")]
+    #[test]
+    fn test_ntt_verification_r1cs_512() {
+        assert_eq!(N, 512);
+
+        let keypair = KeyPair::keygen_with_seed("test seed".as_ref());
+        let message = "testing message".as_bytes();
+        let sig = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), message.as_ref());
+        assert!(keypair.public_key.verify_rust(message.as_ref(), &sig));
+
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let falcon_circuit =
+            FalconNTTVerificationCircuit::build_circuit(keypair.public_key, message.to_vec(), sig);
+        falcon_circuit.generate_constraints(cs.clone()).unwrap();
+
+        // the constant one, pk_ntt and hm_ntt
+        assert_eq!(cs.num_instance_variables(), 2 * 512 + 1);
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[cfg(feature = "falcon-1024")]
+    #[test]
+    fn test_ntt_verification_r1cs_test_vectors() {
+        for (pk, message, sig) in test_vectors() {
+            assert!(pk.verify_rust(message.as_ref(), &sig));
+
+            let cs = ConstraintSystem::<Fq>::new_ref();
+            let falcon_circuit = FalconNTTVerificationCircuit::build_circuit(pk, message, sig);
+            falcon_circuit.generate_constraints(cs.clone()).unwrap();
+            assert!(cs.is_satisfied().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_ntt_verification_r1cs_sign_with_witness() {
+        let keypair = KeyPair::keygen();
+        let message = "testing message".as_bytes();
+        let (sig, v) = keypair.secret_key.sign_with_witness(
+            "test seed".as_ref(),
+            message,
+            &keypair.public_key,
+        );
+
+        // the circuit witnesses v as hm - sig * pk
+        let hm = Polynomial::from_hash_of_message(message, sig.nonce());
+        let sig_poly: Polynomial = (&sig).into();
+        assert_eq!(v, hm - sig_poly * Polynomial::from(&keypair.public_key));
+
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let falcon_circuit =
+            FalconNTTVerificationCircuit::build_circuit(keypair.public_key, message.to_vec(), sig);
+        falcon_circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_estimated_constraints() {
+        let keypair = KeyPair::keygen();
+        let message = "testing message".as_bytes();
+        let sig = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), message.as_ref());
+
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let falcon_circuit =
+            FalconNTTVerificationCircuit::build_circuit(keypair.public_key, message.to_vec(), sig);
+        falcon_circuit.generate_constraints(cs.clone()).unwrap();
+
+        let estimate = FalconNTTVerificationCircuit::estimated_constraints();
+        assert_eq!(estimate, cs.num_constraints());
+
+        // the counts reported in the README
+        #[cfg(feature = "falcon-512")]
+        assert_eq!(estimate, 81460);
+        #[cfg(not(feature = "falcon-512"))]
+        assert_eq!(estimate, 162870);
+    }
+
+    #[test]
+    fn test_ntt_verification_r1cs_synth_tuple() {
+        let keypair = KeyPair::keygen();
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+
+        for &(target_norm, satisfied) in [
+            (SIG_L2_BOUND / 2, true),
+            (SIG_L2_BOUND - 1, true),
+            (SIG_L2_BOUND + 10000, false),
+        ]
+        .iter()
+        {
+            let (sig, _v, hm) =
+                Polynomial::synth_verification_tuple(&keypair.public_key, &mut rng, target_norm);
+
+            let cs = ConstraintSystem::<Fq>::new_ref();
+            let falcon_circuit =
+                FalconNTTVerificationCircuit::build_circuit_from_polys(keypair.public_key, hm, sig);
+            falcon_circuit.generate_constraints(cs.clone()).unwrap();
+
+            assert_eq!(cs.is_satisfied().unwrap(), satisfied);
+        }
+    }
+
+    #[test]
+    fn test_ntt_verification_r1cs_over_norm() {
+        let keypair = KeyPair::keygen();
+        let message = "testing message".as_bytes();
+        let sig = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), message.as_ref());
+        let bad_sig = sig.over_norm_for_testing(&keypair.public_key, message);
+        assert!(!keypair.public_key.verify_rust(message, &bad_sig));
+
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let falcon_circuit = FalconNTTVerificationCircuit::build_circuit(
+            keypair.public_key,
+            message.to_vec(),
+            bad_sig,
+        );
+        falcon_circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_synthesis_error_propagation() {
+        let keypair = KeyPair::keygen();
+        let message = "testing message".as_bytes();
+        let sig = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), message.as_ref());
+
+        // without a constraint system, ntt_param_var fails before any
+        // witness is allocated; the error has to be returned, where
+        // it used to be unwrapped
+        let cs = ConstraintSystemRef::<Fq>::None;
+
+        let falcon_circuit =
+            FalconNTTVerificationCircuit::build_circuit(keypair.public_key, message.to_vec(), sig);
+
+        match falcon_circuit.generate_constraints(cs) {
+            Err(SynthesisError::MissingCS) => (),
+            res => panic!("unexpected result: {:?}", res),
+        }
     }
 }
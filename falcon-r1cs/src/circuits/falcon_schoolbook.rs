@@ -15,6 +15,18 @@ impl FalconSchoolBookVerificationCircuit {
     pub fn build_circuit(pk: PublicKey, msg: Vec<u8>, sig: Signature) -> Self {
         Self { pk, msg, sig }
     }
+
+    /// The public input blocks (name, length) in allocation order:
+    /// pk and hm, in the coefficient domain
+    pub fn public_input_layout() -> Vec<(&'static str, usize)> {
+        vec![("pk", N), ("hm", N)]
+    }
+
+    /// The number of public field elements a verifier expects: the
+    /// total length of the layout. The constant one is not included.
+    pub fn num_public_inputs() -> usize {
+        Self::public_input_layout().iter().map(|(_, n)| n).sum()
+    }
 }
 
 impl<F: PrimeField> ConstraintSynthesizer<F> for FalconSchoolBookVerificationCircuit {
@@ -27,7 +39,7 @@ impl<F: PrimeField> ConstraintSynthesizer<F> for FalconSchoolBookVerificationCir
         let sig_poly: Polynomial = (&self.sig).into();
         let pk_poly: Polynomial = (&self.pk).into();
 
-        let const_q_var = FpVar::<F>::new_constant(cs.clone(), F::from(MODULUS))?;
+        let const_q_var = modulus_var(cs.clone())?;
 
         // ========================================
         // compute related data in the clear
@@ -146,6 +158,7 @@ mod tests {
             .secret_key
             .sign_with_seed("test seed".as_ref(), message.as_ref());
 
+        #[cfg(feature = "ffi")]
         assert!(keypair.public_key.verify(message.as_ref(), &sig));
         assert!(keypair.public_key.verify_rust(message.as_ref(), &sig));
 
@@ -166,5 +179,35 @@ mod tests {
         // );
 
         assert!(cs.is_satisfied().unwrap());
+
+        let layout = FalconSchoolBookVerificationCircuit::public_input_layout();
+        assert_eq!(
+            layout.iter().map(|(_, len)| len).sum::<usize>(),
+            cs.num_instance_variables() - 1
+        );
+        assert_eq!(
+            FalconSchoolBookVerificationCircuit::num_public_inputs(),
+            cs.num_instance_variables() - 1
+        );
+    }
+
+    #[test]
+    fn test_schoolbook_verification_r1cs_over_norm() {
+        let keypair = KeyPair::keygen();
+        let message = "testing message".as_bytes();
+        let sig = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), message.as_ref());
+        let bad_sig = sig.over_norm_for_testing(&keypair.public_key, message);
+        assert!(!keypair.public_key.verify_rust(message, &bad_sig));
+
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let falcon_circuit = FalconSchoolBookVerificationCircuit::build_circuit(
+            keypair.public_key,
+            message.to_vec(),
+            bad_sig,
+        );
+        falcon_circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
     }
 }
@@ -1,7 +1,11 @@
 mod falcon_dual_ntt;
+mod falcon_multi_message;
 mod falcon_ntt;
 mod falcon_schoolbook;
+mod public_inputs;
 
 pub use falcon_dual_ntt::FalconDualNTTVerificationCircuit;
+pub use falcon_multi_message::FalconMultiMessageCircuit;
 pub use falcon_ntt::FalconNTTVerificationCircuit;
 pub use falcon_schoolbook::FalconSchoolBookVerificationCircuit;
+pub use public_inputs::FalconPublicInputs;
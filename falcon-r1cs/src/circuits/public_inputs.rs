@@ -0,0 +1,104 @@
+use ark_ff::PrimeField;
+use falcon_rust::{NTTPolynomial, Polynomial, PublicKey};
+
+/// The public inputs of the NTT based verification circuits, i.e.,
+/// `FalconNTTVerificationCircuit` and `FalconDualNTTVerificationCircuit`:
+/// the coefficients of pk and hm in the NTT domain, in that order.
+///
+/// The coefficients are stored as integers in [0, q), which is how
+/// the circuits allocate them; use `to_field_elements` to obtain the
+/// vector expected by the proof system's verifier.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FalconPublicInputs {
+    pk_ntt: Vec<u16>,
+    hm_ntt: Vec<u16>,
+}
+
+impl FalconPublicInputs {
+    /// Build the public inputs from the public key, the message
+    /// and the nonce of the signature
+    pub fn new(pk: &PublicKey, msg: &[u8], nonce: &[u8]) -> Self {
+        let hm = Polynomial::from_hash_of_message(msg, nonce);
+        Self::from_polys(pk, &hm)
+    }
+
+    /// Build the public inputs from the public key and the
+    /// hash of the message
+    pub fn from_polys(pk: &PublicKey, hm: &Polynomial) -> Self {
+        Self {
+            pk_ntt: pk.to_ntt().coeff().to_vec(),
+            hm_ntt: NTTPolynomial::from(hm).coeff().to_vec(),
+        }
+    }
+
+    /// Coefficients of the public key in the NTT domain
+    pub fn pk_ntt(&self) -> &[u16] {
+        &self.pk_ntt
+    }
+
+    /// Coefficients of the hash of the message in the NTT domain
+    pub fn hm_ntt(&self) -> &[u16] {
+        &self.hm_ntt
+    }
+
+    /// The ordered field elements to be passed to the verifier
+    pub fn to_field_elements<F: PrimeField>(&self) -> Vec<F> {
+        self.pk_ntt
+            .iter()
+            .chain(self.hm_ntt.iter())
+            .map(|&e| F::from(e))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FalconNTTVerificationCircuit;
+    use ark_ed_on_bls12_381::fq::Fq;
+    use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
+    use falcon_rust::KeyPair;
+
+    #[test]
+    fn test_public_inputs() {
+        let keypair = KeyPair::keygen();
+        let message = "testing message".as_bytes();
+        let sig = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), message.as_ref());
+
+        let public_inputs = FalconPublicInputs::new(&keypair.public_key, message, sig.nonce());
+
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let falcon_circuit =
+            FalconNTTVerificationCircuit::build_circuit(keypair.public_key, message.to_vec(), sig);
+        falcon_circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+
+        // the first instance variable is the constant one
+        assert_eq!(
+            cs.borrow().unwrap().instance_assignment[1..],
+            public_inputs.to_field_elements::<Fq>()[..]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_public_inputs_serde() {
+        let keypair = KeyPair::keygen();
+        let message = "testing message".as_bytes();
+        let sig = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), message.as_ref());
+        let public_inputs = FalconPublicInputs::new(&keypair.public_key, message, sig.nonce());
+
+        let json = serde_json::to_string(&public_inputs).unwrap();
+        let rec: FalconPublicInputs = serde_json::from_str(&json).unwrap();
+        assert_eq!(public_inputs, rec);
+        assert_eq!(
+            public_inputs.to_field_elements::<Fq>(),
+            rec.to_field_elements::<Fq>()
+        );
+    }
+}
@@ -0,0 +1,249 @@
+//! Export of the R1CS matrices to the `.r1cs` binary format of circom,
+//! so that the circuits can be fed to non-arkworks tooling such as snarkjs.
+//!
+//! The wires are numbered as in arkworks: wire 0 is the constant one,
+//! followed by the instance variables (exported as public inputs) and
+//! the witness variables (exported as internal signals).
+
+use ark_ff::{BigInteger, FpParameters, PrimeField};
+use ark_relations::r1cs::{
+    ConstraintMatrices, ConstraintSynthesizer, ConstraintSystem, SynthesisError,
+};
+use std::{
+    collections::HashMap,
+    io::{self, Read, Write},
+};
+
+const MAGIC: &[u8; 4] = b"r1cs";
+const VERSION: u32 = 1;
+const HEADER_SECTION: u32 = 1;
+const CONSTRAINT_SECTION: u32 = 2;
+const WIRE_TO_LABEL_SECTION: u32 = 3;
+
+/// Synthesize the circuit and return its (A, B, C) matrices
+pub fn circuit_matrices<F: PrimeField, C: ConstraintSynthesizer<F>>(
+    circuit: C,
+) -> Result<ConstraintMatrices<F>, SynthesisError> {
+    let cs = ConstraintSystem::<F>::new_ref();
+    circuit.generate_constraints(cs.clone())?;
+    // inline the linear combinations so that the rows only refer to variables
+    cs.finalize();
+    Ok(cs
+        .to_matrices()
+        .expect("matrices are constructed in the default synthesis mode"))
+}
+
+/// Write the matrices in the circom `.r1cs` binary format
+pub fn export_r1cs<F: PrimeField, W: Write>(
+    matrices: &ConstraintMatrices<F>,
+    mut writer: W,
+) -> io::Result<()> {
+    let modulus = F::Params::MODULUS.to_bytes_le();
+    let n8 = modulus.len();
+    let num_wires = matrices.num_instance_variables + matrices.num_witness_variables;
+
+    writer.write_all(MAGIC)?;
+    write_u32(&mut writer, VERSION)?;
+    write_u32(&mut writer, 3)?;
+
+    // header
+    write_u32(&mut writer, HEADER_SECTION)?;
+    write_u64(&mut writer, (32 + n8) as u64)?;
+    write_u32(&mut writer, n8 as u32)?;
+    writer.write_all(&modulus)?;
+    write_u32(&mut writer, num_wires as u32)?;
+    // no public outputs
+    write_u32(&mut writer, 0)?;
+    // public inputs, excluding the constant one
+    write_u32(&mut writer, (matrices.num_instance_variables - 1) as u32)?;
+    // no private inputs: the witnesses are all internal signals
+    write_u32(&mut writer, 0)?;
+    write_u64(&mut writer, num_wires as u64)?;
+    write_u32(&mut writer, matrices.num_constraints as u32)?;
+
+    // constraints
+    let num_non_zero = matrices.a_num_non_zero + matrices.b_num_non_zero + matrices.c_num_non_zero;
+    write_u32(&mut writer, CONSTRAINT_SECTION)?;
+    write_u64(
+        &mut writer,
+        (12 * matrices.num_constraints + (4 + n8) * num_non_zero) as u64,
+    )?;
+    for i in 0..matrices.num_constraints {
+        for row in [&matrices.a[i], &matrices.b[i], &matrices.c[i]].iter() {
+            write_u32(&mut writer, row.len() as u32)?;
+            for (coeff, index) in row.iter() {
+                write_u32(&mut writer, *index as u32)?;
+                writer.write_all(&coeff.into_repr().to_bytes_le())?;
+            }
+        }
+    }
+
+    // the label of a wire is its index
+    write_u32(&mut writer, WIRE_TO_LABEL_SECTION)?;
+    write_u64(&mut writer, 8 * num_wires as u64)?;
+    for i in 0..num_wires {
+        write_u64(&mut writer, i as u64)?;
+    }
+
+    Ok(())
+}
+
+/// Read the matrices from the circom `.r1cs` binary format.
+/// Returns an `InvalidData` error if the input is malformed, or
+/// if it is defined over a field other than F.
+pub fn import_r1cs<F: PrimeField, R: Read>(mut reader: R) -> io::Result<ConstraintMatrices<F>> {
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(invalid_data("not an r1cs file"));
+    }
+    if read_u32(&mut reader)? != VERSION {
+        return Err(invalid_data("unsupported r1cs version"));
+    }
+
+    // the sections may come in any order
+    let num_sections = read_u32(&mut reader)?;
+    let mut sections = HashMap::new();
+    for _ in 0..num_sections {
+        let section_type = read_u32(&mut reader)?;
+        let size = read_u64(&mut reader)?;
+        let mut section = Vec::new();
+        (&mut reader).take(size).read_to_end(&mut section)?;
+        if section.len() as u64 != size {
+            return Err(invalid_data("truncated section"));
+        }
+        sections.insert(section_type, section);
+    }
+
+    // header
+    let mut header = sections
+        .get(&HEADER_SECTION)
+        .ok_or_else(|| invalid_data("missing header section"))?
+        .as_slice();
+    let n8 = read_u32(&mut header)? as usize;
+    let mut modulus = vec![0u8; n8];
+    header.read_exact(&mut modulus)?;
+    if modulus != F::Params::MODULUS.to_bytes_le() {
+        return Err(invalid_data("field mismatch"));
+    }
+    let num_wires = read_u32(&mut header)? as usize;
+    let num_pub_out = read_u32(&mut header)? as usize;
+    let num_pub_in = read_u32(&mut header)? as usize;
+    let _num_prv_in = read_u32(&mut header)?;
+    let _num_labels = read_u64(&mut header)?;
+    let num_constraints = read_u32(&mut header)? as usize;
+    let num_instance_variables = 1 + num_pub_out + num_pub_in;
+    if num_instance_variables > num_wires {
+        return Err(invalid_data("inconsistent number of wires"));
+    }
+
+    // constraints
+    let mut constraints = sections
+        .get(&CONSTRAINT_SECTION)
+        .ok_or_else(|| invalid_data("missing constraint section"))?
+        .as_slice();
+    let mut matrices = [Vec::new(), Vec::new(), Vec::new()];
+    let mut coeff_bytes = vec![0u8; n8];
+    for _ in 0..num_constraints {
+        for matrix in matrices.iter_mut() {
+            let len = read_u32(&mut constraints)?;
+            let mut row = Vec::new();
+            for _ in 0..len {
+                let index = read_u32(&mut constraints)? as usize;
+                if index >= num_wires {
+                    return Err(invalid_data("wire index out of range"));
+                }
+                constraints.read_exact(&mut coeff_bytes)?;
+                row.push((F::from_le_bytes_mod_order(&coeff_bytes), index));
+            }
+            matrix.push(row);
+        }
+    }
+    let [a, b, c] = matrices;
+
+    Ok(ConstraintMatrices {
+        num_instance_variables,
+        num_witness_variables: num_wires - num_instance_variables,
+        num_constraints,
+        a_num_non_zero: a.iter().map(|row| row.len()).sum(),
+        b_num_non_zero: b.iter().map(|row| row.len()).sum(),
+        c_num_non_zero: c.iter().map(|row| row.len()).sum(),
+        a,
+        b,
+        c,
+    })
+}
+
+fn write_u32<W: Write>(writer: &mut W, x: u32) -> io::Result<()> {
+    writer.write_all(&x.to_le_bytes())
+}
+
+fn write_u64<W: Write>(writer: &mut W, x: u64) -> io::Result<()> {
+    writer.write_all(&x.to_le_bytes())
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> io::Result<u32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FalconNTTVerificationCircuit;
+    use ark_ed_on_bls12_381::fq::Fq;
+    use falcon_rust::KeyPair;
+
+    #[test]
+    fn test_export_r1cs() {
+        let keypair = KeyPair::keygen();
+        let message = "testing message".as_bytes();
+        let sig = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), message.as_ref());
+        let falcon_circuit =
+            FalconNTTVerificationCircuit::build_circuit(keypair.public_key, message.to_vec(), sig);
+
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        falcon_circuit
+            .clone()
+            .generate_constraints(cs.clone())
+            .unwrap();
+        assert!(cs.is_satisfied().unwrap());
+
+        let matrices = circuit_matrices::<Fq, _>(falcon_circuit).unwrap();
+        let mut buf = Vec::new();
+        export_r1cs(&matrices, &mut buf).unwrap();
+        assert_eq!(&buf[0..4], MAGIC);
+
+        let rec = import_r1cs::<Fq, _>(buf.as_slice()).unwrap();
+        assert_eq!(rec.num_constraints, cs.num_constraints());
+        assert_eq!(rec.num_instance_variables, cs.num_instance_variables());
+        assert_eq!(rec.num_witness_variables, cs.num_witness_variables());
+        assert_eq!(rec.a.len(), cs.num_constraints());
+        assert_eq!(rec.b.len(), cs.num_constraints());
+        assert_eq!(rec.c.len(), cs.num_constraints());
+        assert_eq!(rec.a_num_non_zero, matrices.a_num_non_zero);
+        assert_eq!(rec.b_num_non_zero, matrices.b_num_non_zero);
+        assert_eq!(rec.c_num_non_zero, matrices.c_num_non_zero);
+        assert_eq!(rec.a, matrices.a);
+        assert_eq!(rec.b, matrices.b);
+        assert_eq!(rec.c, matrices.c);
+
+        // a truncated file is rejected
+        assert!(import_r1cs::<Fq, _>(&buf[..buf.len() - 1]).is_err());
+        // so is a file over a different field
+        assert!(import_r1cs::<ark_bls12_381::Fq, _>(buf.as_slice()).is_err());
+    }
+}
@@ -2,9 +2,56 @@ use super::*;
 use ark_ff::PrimeField;
 use ark_r1cs_std::{alloc::AllocVar, fields::fp::FpVar, prelude::*};
 use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
-use falcon_rust::{MODULUS, N};
+use falcon_rust::{LOG_N, MODULUS, N};
 use num_bigint::BigUint;
 
+/// Whether the gadgets of this crate are sound over F.
+///
+/// The mod q gadgets and the NTT circuit compute their intermediate values
+/// as native field elements, and rely on these never wrapping around F's
+/// modulus. The largest such value is 2^LOG_N * q^(LOG_N + 1), built by
+/// the NTT circuit, which takes 145 bits (falcon-512) or 160 bits
+/// (falcon-1024). The scalar fields of BLS12-381 and BN254 are supported;
+/// small fields such as the 64 bits Goldilocks field are not, and the
+/// gadgets panic when used over them.
+pub fn field_is_supported<F: PrimeField>() -> bool {
+    field_holds_bits(F::size_in_bits(), ntt_bound_bits())
+}
+
+// Whether a field whose modulus has modulus_bits bits holds any value of
+// value_bits bits without wrapping around; the modulus is at least
+// 2^(modulus_bits - 1).
+fn field_holds_bits(modulus_bits: usize, value_bits: u64) -> bool {
+    modulus_bits as u64 > value_bits
+}
+
+// The number of bits of 2^LOG_N * q^(LOG_N + 1), the bound on the
+// intermediate values of the NTT circuit.
+fn ntt_bound_bits() -> u64 {
+    (BigUint::from(MODULUS).pow(LOG_N as u32 + 1) << LOG_N).bits()
+}
+
+// Panic unless F holds the sum of len products of two elements in [0, q).
+fn assert_field_holds_products<F: PrimeField>(len: usize) {
+    let max_product = (MODULUS as u64 - 1) * (MODULUS as u64 - 1);
+    let max_sum_bits = (BigUint::from(max_product) * len).bits();
+    assert!(
+        field_holds_bits(F::size_in_bits(), max_sum_bits),
+        "field of {} bits is too small for {} products mod q",
+        F::size_in_bits(),
+        len
+    );
+}
+
+/// Panic unless the NTT circuit is sound over F; see `field_is_supported`.
+pub(crate) fn assert_field_holds_ntt<F: PrimeField>() {
+    assert!(
+        field_is_supported::<F>(),
+        "field of {} bits is too small for the NTT circuit",
+        F::size_in_bits()
+    );
+}
+
 /// Generate the variables c = a * B mod 12289;
 /// with a guarantee that the inputs a and b satisfies:
 /// * a is a dim n vector with a_i < 12289
@@ -40,6 +87,7 @@ pub(crate) fn inner_product_mod<F: PrimeField>(
     if a.len() != b.len() || a.is_empty() {
         panic!("Invalid input length: a {} vs b {}", a.len(), b.len());
     }
+    assert_field_holds_products::<F>(a.len());
 
     // we want to prove that `c = <a \cdot b> mod 12289`
     // that is
@@ -148,6 +196,42 @@ pub fn mod_q<F: PrimeField>(
     Ok(b_var)
 }
 
+/// Reduce each of the variables mod 12289, in place.
+/// Cost: 29 constraints per variable
+///
+/// Compared to calling `mod_q` on each variable, the reduced value
+/// b = a - t * 12289 is kept as a linear combination rather than
+/// allocated as a new witness, which saves the equality constraint.
+pub fn reduce_many<F: PrimeField>(
+    cs: ConstraintSystemRef<F>,
+    vars: &mut [FpVar<F>],
+    modulus_var: &FpVar<F>,
+) -> Result<(), SynthesisError> {
+    let modulus_int: BigUint = F::from(MODULUS).into();
+
+    for a in vars.iter_mut() {
+        // rebuild the field elements
+        let a_val = if cs.is_in_setup_mode() {
+            F::one()
+        } else {
+            a.value()?
+        };
+        let a_int: BigUint = a_val.into();
+        let t_val = F::from(&a_int / &modulus_int);
+
+        // b = a - t * 12289, as a linear combination
+        let t_var = FpVar::<F>::new_witness(cs.clone(), || Ok(t_val))?;
+        let b_var = &*a - t_var * modulus_var;
+
+        // b < 12289
+        enforce_less_than_q(cs.clone(), &b_var)?;
+
+        *a = b_var;
+    }
+
+    Ok(())
+}
+
 /// Generate the variable c = a * b mod 12289;
 /// with a guarantee that the inputs a and b satisfies:
 /// * a < 12289
@@ -169,6 +253,7 @@ pub(crate) fn mul_mod<F: PrimeField>(
     // Note that this implementation assumes the
     // native field's order is greater than 12289^2
     // so we do not have any overflows
+    assert_field_holds_products::<F>(1);
 
     // rebuild the field elements
     let a_val = if cs.is_in_setup_mode() {
@@ -315,7 +400,7 @@ mod tests {
             let b = Fq::from($b);
 
             let a_var = FpVar::<Fq>::new_witness(cs.clone(), || Ok(a)).unwrap();
-            let const_q_var = FpVar::<Fq>::new_constant(cs.clone(), Fq::from(MODULUS)).unwrap();
+            let const_q_var = modulus_var(cs.clone()).unwrap();
 
             // let num_instance_variables = cs.num_instance_variables();
             // let num_witness_variables = cs.num_witness_variables();
@@ -372,6 +457,50 @@ mod tests {
         // assert!(false)
     }
 
+    #[test]
+    fn test_reduce_many() {
+        let mut rng = test_rng();
+        let values: Vec<Fq> = (0..100)
+            .map(|_| Fq::from(rng.gen_range(0u64..1 << 40)))
+            .collect();
+
+        // per-element mod_q
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let const_q_var = modulus_var(cs.clone()).unwrap();
+        let vars = values
+            .iter()
+            .map(|&x| FpVar::<Fq>::new_witness(cs.clone(), || Ok(x)).unwrap())
+            .collect::<Vec<_>>();
+        let num_constraints = cs.num_constraints();
+        let reduced = vars
+            .iter()
+            .map(|x| mod_q(cs.clone(), x, &const_q_var).unwrap())
+            .collect::<Vec<_>>();
+        let mod_q_num_constraints = cs.num_constraints() - num_constraints;
+        assert!(cs.is_satisfied().unwrap());
+
+        // batched
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let const_q_var = modulus_var(cs.clone()).unwrap();
+        let mut vars = values
+            .iter()
+            .map(|&x| FpVar::<Fq>::new_witness(cs.clone(), || Ok(x)).unwrap())
+            .collect::<Vec<_>>();
+        let num_constraints = cs.num_constraints();
+        reduce_many(cs.clone(), &mut vars, &const_q_var).unwrap();
+        let reduce_many_num_constraints = cs.num_constraints() - num_constraints;
+        assert!(cs.is_satisfied().unwrap());
+
+        for (a, b) in reduced.iter().zip(vars.iter()) {
+            assert_eq!(a.value().unwrap(), b.value().unwrap());
+        }
+        // one constraint saved per element
+        assert_eq!(
+            reduce_many_num_constraints + values.len(),
+            mod_q_num_constraints
+        );
+    }
+
     macro_rules! test_mul_mod {
         ($a:expr, $b:expr, $c:expr, $satisfied:expr) => {
             let cs = ConstraintSystem::<Fq>::new_ref();
@@ -381,7 +510,7 @@ mod tests {
 
             let a_var = FpVar::<Fq>::new_witness(cs.clone(), || Ok(a)).unwrap();
             let b_var = FpVar::<Fq>::new_witness(cs.clone(), || Ok(b)).unwrap();
-            let const_q_var = FpVar::<Fq>::new_constant(cs.clone(), Fq::from(MODULUS)).unwrap();
+            let const_q_var = modulus_var(cs.clone()).unwrap();
 
             // let num_instance_variables = cs.num_instance_variables();
             // let num_witness_variables = cs.num_witness_variables();
@@ -409,6 +538,27 @@ mod tests {
         };
     }
 
+    #[test]
+    fn test_field_is_supported() {
+        // the scalar field of BLS12-381
+        assert!(field_is_supported::<Fq>());
+        // BN254's scalar field has 254 bits
+        assert!(field_holds_bits(254, ntt_bound_bits()));
+
+        #[cfg(feature = "falcon-1024")]
+        assert_eq!(ntt_bound_bits(), 160);
+        #[cfg(feature = "falcon-512")]
+        assert_eq!(ntt_bound_bits(), 145);
+
+        // a 64 bits field holds a single product, but not the NTT
+        assert!(field_holds_bits(64, 28));
+        assert!(!field_holds_bits(64, ntt_bound_bits()));
+        assert!(!field_holds_bits(
+            ntt_bound_bits() as usize,
+            ntt_bound_bits()
+        ));
+    }
+
     #[test]
     fn test_mul_mod() {
         // =======================
@@ -443,7 +593,7 @@ mod tests {
 
             let a_var = FpVar::<Fq>::new_witness(cs.clone(), || Ok(a)).unwrap();
             let b_var = FpVar::<Fq>::new_witness(cs.clone(), || Ok(b)).unwrap();
-            let const_q_var = FpVar::<Fq>::new_constant(cs.clone(), Fq::from(MODULUS)).unwrap();
+            let const_q_var = modulus_var(cs.clone()).unwrap();
 
             // let num_instance_variables = cs.num_instance_variables();
             // let num_witness_variables = cs.num_witness_variables();
@@ -515,7 +665,7 @@ mod tests {
 
             let a_var = FpVar::<Fq>::new_witness(cs.clone(), || Ok(a)).unwrap();
             let b_var = FpVar::<Fq>::new_witness(cs.clone(), || Ok(b)).unwrap();
-            let const_q_var = FpVar::<Fq>::new_constant(cs.clone(), Fq::from(MODULUS)).unwrap();
+            let const_q_var = modulus_var(cs.clone()).unwrap();
 
             // let num_instance_variables = cs.num_instance_variables();
             // let num_witness_variables = cs.num_witness_variables();
@@ -620,7 +770,7 @@ mod tests {
                 .iter()
                 .map(|x| FpVar::<Fq>::new_witness(cs.clone(), || Ok(x)).unwrap())
                 .collect();
-            let const_q_var = FpVar::<Fq>::new_constant(cs.clone(), Fq::from(MODULUS)).unwrap();
+            let const_q_var = modulus_var(cs.clone()).unwrap();
 
             // let num_instance_variables = cs.num_instance_variables();
             // let num_witness_variables = cs.num_witness_variables();
@@ -684,7 +834,7 @@ mod tests {
                             .collect::<Vec<FpVar<Fq>>>()
                     })
                     .collect();
-                let const_q_var = FpVar::<Fq>::new_constant(cs.clone(), Fq::from(MODULUS)).unwrap();
+                let const_q_var = modulus_var(cs.clone()).unwrap();
 
                 let b_var_ref: Vec<&[FpVar<Fq>]> = b_var.iter().map(|x| x.as_ref()).collect();
 
@@ -0,0 +1,153 @@
+use crate::{less_than_q_bits, PolyVar};
+use ark_crypto_primitives::commitment::{
+    pedersen::{
+        constraints::{CommGadget, ParametersVar, RandomnessVar},
+        Commitment, Parameters, Randomness, Window,
+    },
+    CommitmentGadget, CommitmentScheme,
+};
+use ark_ed_on_bls12_381::{constraints::EdwardsVar, EdwardsAffine, EdwardsProjective, Fq};
+use ark_r1cs_std::prelude::*;
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+use ark_std::rand::Rng;
+use falcon_rust::{Polynomial, N};
+
+/// The windows of the Pedersen commitment to a polynomial: each
+/// coefficient is committed on 16 bits, so the input is 16N bits.
+#[derive(Clone)]
+pub struct PolyCommitmentWindow;
+
+impl Window for PolyCommitmentWindow {
+    const WINDOW_SIZE: usize = 128;
+    const NUM_WINDOWS: usize = 16 * N / 128;
+}
+
+/// A Pedersen commitment over the Jubjub curve
+pub type PolyCommitment = Commitment<EdwardsProjective, PolyCommitmentWindow>;
+
+type PolyCommitmentGadget = CommGadget<EdwardsProjective, EdwardsVar, PolyCommitmentWindow>;
+type PolyCommitmentParamsVar = ParametersVar<EdwardsProjective, EdwardsVar>;
+
+/// Sample the generators of `PolyCommitment`.
+/// The commitment is binding as long as the discrete logarithms between
+/// the generators are unknown, so the rng should be seeded from a public
+/// value, e.g., a hash of a nothing-up-my-sleeve string.
+pub fn poly_commitment_setup<R: Rng>(rng: &mut R) -> Parameters<EdwardsProjective> {
+    PolyCommitment::setup(rng).unwrap()
+}
+
+/// Commit to the coefficients of a polynomial (e.g., of a signature),
+/// each encoded on 16 bits, little endian. The commitment is hiding
+/// thanks to the randomness, and binding under the discrete logarithm
+/// assumption on Jubjub.
+pub fn poly_commitment(
+    params: &Parameters<EdwardsProjective>,
+    poly: &Polynomial,
+    randomness: &Randomness<EdwardsProjective>,
+) -> EdwardsAffine {
+    let input: Vec<u8> = poly
+        .coeff()
+        .iter()
+        .flat_map(|c| c.to_le_bytes().to_vec())
+        .collect();
+    PolyCommitment::commit(params, &input, randomness).unwrap()
+}
+
+/// Constraint that commitment is the `poly_commitment` of the witnessed
+/// polynomial, with the same parameters and the witnessed randomness.
+/// Each coefficient is range checked to [0, 12289), so that it has a
+/// unique encoding as the input of the commitment.
+/// Cost: 29 constraints per coefficient for the range proofs, plus the
+/// Pedersen commitment.
+pub fn enforce_poly_commitment(
+    cs: ConstraintSystemRef<Fq>,
+    poly_var: &PolyVar<Fq>,
+    params: &Parameters<EdwardsProjective>,
+    randomness: &Randomness<EdwardsProjective>,
+    commitment: &EdwardsVar,
+) -> Result<(), SynthesisError> {
+    if poly_var.coeff().len() != N {
+        panic!("Invalid input length: {}", poly_var.coeff().len());
+    }
+
+    // the 14 bits of every coefficient, padded to two bytes
+    let mut input = Vec::with_capacity(2 * N);
+    for c in poly_var.coeff() {
+        let mut bits = less_than_q_bits(cs.clone(), c)?;
+        bits.resize(16, Boolean::FALSE);
+        input.push(UInt8::from_bits_le(&bits[..8]));
+        input.push(UInt8::from_bits_le(&bits[8..]));
+    }
+
+    let params_var = PolyCommitmentParamsVar::new_constant(cs.clone(), params)?;
+    let randomness_var = RandomnessVar::<Fq>::new_witness(cs, || Ok(randomness))?;
+
+    PolyCommitmentGadget::commit(&params_var, &input, &randomness_var)?.enforce_equal(commitment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ed_on_bls12_381::Fr;
+    use ark_r1cs_std::fields::fp::FpVar;
+    use ark_relations::r1cs::ConstraintSystem;
+    use ark_std::{test_rng, UniformRand};
+    use falcon_rust::{KeyPair, MODULUS};
+
+    #[test]
+    fn test_enforce_poly_commitment() {
+        let mut rng = test_rng();
+        let params = poly_commitment_setup(&mut rng);
+
+        let keypair = KeyPair::keygen_with_seed("test seed".as_ref());
+        let message = "testing message".as_bytes();
+        let sig = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), message);
+        let sig_poly: Polynomial = (&sig).into();
+        let randomness = Randomness(Fr::rand(&mut rng));
+        let commitment = poly_commitment(&params, &sig_poly, &randomness);
+
+        let sig2 = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), "another message".as_ref());
+        let sig2_poly: Polynomial = (&sig2).into();
+        assert_ne!(sig_poly, sig2_poly);
+        let randomness2 = Randomness(Fr::rand(&mut rng));
+
+        // the coefficients of sig, with the first one shifted by q,
+        // i.e., the same polynomial mod q but out of range
+        let mut shifted: Vec<Fq> = sig_poly.coeff().iter().map(|&c| Fq::from(c)).collect();
+        shifted[0] += Fq::from(MODULUS);
+
+        let sig_coeffs: Vec<Fq> = sig_poly.coeff().iter().map(|&c| Fq::from(c)).collect();
+        let sig2_coeffs: Vec<Fq> = sig2_poly.coeff().iter().map(|&c| Fq::from(c)).collect();
+
+        for (coeffs, r, is_correct) in [
+            // the honest opening
+            (&sig_coeffs, &randomness, true),
+            // second openings, to another polynomial
+            (&sig2_coeffs, &randomness, false),
+            (&sig2_coeffs, &randomness2, false),
+            // the same polynomial with other randomness
+            (&sig_coeffs, &randomness2, false),
+            // an unreduced coefficient
+            (&shifted, &randomness, false),
+        ]
+        .iter()
+        {
+            let cs = ConstraintSystem::<Fq>::new_ref();
+            let poly_var = PolyVar(
+                coeffs
+                    .iter()
+                    .map(|&c| FpVar::<Fq>::new_witness(cs.clone(), || Ok(c)))
+                    .collect::<Result<Vec<_>, _>>()
+                    .unwrap(),
+            );
+            let commitment_var = EdwardsVar::new_input(cs.clone(), || Ok(commitment)).unwrap();
+
+            enforce_poly_commitment(cs.clone(), &poly_var, &params, r, &commitment_var).unwrap();
+            assert_eq!(cs.is_satisfied().unwrap(), *is_correct);
+        }
+    }
+}
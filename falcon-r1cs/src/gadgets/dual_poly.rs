@@ -1,4 +1,4 @@
-use crate::{NTTPolyVar, PolyVar};
+use crate::{reduce_many, NTTPolyVar, PolyVar};
 use ark_ff::PrimeField;
 use ark_r1cs_std::{fields::fp::FpVar, prelude::*};
 use ark_relations::r1cs::{ConstraintSystemRef, Namespace, SynthesisError};
@@ -20,14 +20,48 @@ impl<F: PrimeField> DualPolyVar<F> {
         let pos = PolyVar::<F>::alloc_vars(cs.clone(), &dual_poly.pos, mode)?;
         let neg = PolyVar::<F>::alloc_vars(cs.clone(), &dual_poly.neg, mode)?;
 
-        // we want to ensure that for each index i, either pos[i] or neg[i] is zero
-        let mut acc = &pos.coeff()[0] * &neg.coeff()[0];
-        for (p, n) in pos.coeff().iter().zip(neg.coeff().iter()).skip(1) {
-            acc += p * n;
+        let res = Self { pos, neg };
+        res.enforce_disjoint()?;
+        Ok(res)
+    }
+
+    /// allocate variables for the positive and negative parts of a
+    /// dual polynomial of any length, given as slices, and enforce
+    /// their disjointness.
+    /// Returns `SynthesisError::Unsatisfiable` if the two slices differ
+    /// in length, or if a coefficient is not smaller than MODULUS.
+    pub fn alloc_slice(
+        cs: impl Into<Namespace<F>> + Clone,
+        pos: &[u16],
+        neg: &[u16],
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        if pos.len() != neg.len() {
+            return Err(SynthesisError::Unsatisfiable);
         }
-        acc.is_zero()?.enforce_equal(&Boolean::TRUE)?;
+        let pos = PolyVar::<F>::alloc_slice(cs.clone(), pos, mode)?;
+        let neg = PolyVar::<F>::alloc_slice(cs, neg, mode)?;
 
-        Ok(Self { pos, neg })
+        let res = Self { pos, neg };
+        res.enforce_disjoint()?;
+        Ok(res)
+    }
+
+    /// generate constraints proving that for each index i,
+    /// either pos[i] or neg[i] is zero.
+    /// Cost: 1 constraint per index, for pos[i] * neg[i] = 0.
+    ///
+    /// A single constraint sum_i pos[i] * neg[i] = 0 is not enough:
+    /// the coefficients are not range checked at this point, so a
+    /// prover may choose non-zero products that sum to a multiple of
+    /// the field order, e.g. 1 * 1 + 1 * (-1). It would only be sound
+    /// if all coefficients were first proven smaller than q, as then
+    /// the sum of the products is below N * q^2 and does not wrap.
+    pub fn enforce_disjoint(&self) -> Result<(), SynthesisError> {
+        for (p, n) in self.pos.coeff().iter().zip(self.neg.coeff().iter()) {
+            p.mul_equals(n, &FpVar::zero())?;
+        }
+        Ok(())
     }
 }
 
@@ -44,9 +78,164 @@ impl<F: PrimeField> DualNTTPolyVar<F> {
         const_vars: &[FpVar<F>],
         param: &[FpVar<F>],
     ) -> Result<Self, SynthesisError> {
-        Ok(Self {
-            pos: NTTPolyVar::ntt_circuit(cs.clone(), &input.pos, const_vars, param)?,
-            neg: NTTPolyVar::ntt_circuit(cs.clone(), &input.neg, const_vars, param)?,
-        })
+        let mut pos = NTTPolyVar::ntt_circuit_defer_range_check(&input.pos, const_vars, param)?;
+        let mut neg = NTTPolyVar::ntt_circuit_defer_range_check(&input.neg, const_vars, param)?;
+
+        // final mod reductions, without an extra witness per coefficient
+        reduce_many(cs.clone(), &mut pos.0, &const_vars[0])?;
+        reduce_many(cs, &mut neg.0, &const_vars[0])?;
+
+        Ok(Self { pos, neg })
+    }
+
+    /// generate constraints proving that pos - neg = combined mod q.
+    /// Requires the coefficients of pos, neg and combined to be smaller
+    /// than q, as is the case for the outputs of `ntt_circuit` and of
+    /// `NTTPolyVar::alloc_slice`; then d = combined + neg - pos lies in
+    /// (-q, 2q), and it is a multiple of q iff it is 0 or q.
+    /// Cost: 1 constraint per coefficient, for d * (d - q) = 0
+    pub fn enforce_recombines_to(
+        &self,
+        combined: &NTTPolyVar<F>,
+        modulus_var: &FpVar<F>,
+    ) -> Result<(), SynthesisError> {
+        for ((p, n), c) in self
+            .pos
+            .coeff()
+            .iter()
+            .zip(self.neg.coeff().iter())
+            .zip(combined.coeff().iter())
+        {
+            let d = c + n - p;
+            d.mul_equals(&(&d - modulus_var), &FpVar::zero())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{modulus_var, ntt_param_var};
+    use ark_ed_on_bls12_381::fq::Fq;
+    use ark_ff::{One, Zero};
+    use ark_relations::r1cs::ConstraintSystem;
+    use ark_std::test_rng;
+    use falcon_rust::{power_of_q_field, NTTPolynomial, Polynomial, MODULUS, N};
+
+    #[test]
+    fn test_enforce_disjoint() {
+        let mut rng = test_rng();
+        let poly = Polynomial::rand(&mut rng);
+
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        DualPolyVar::<Fq>::alloc_vars(
+            cs.clone(),
+            &DualPolynomial::from(&poly),
+            AllocationMode::Witness,
+        )
+        .unwrap();
+        assert_eq!(cs.num_constraints(), N);
+        assert!(cs.is_satisfied().unwrap());
+
+        // pos = (1, 1, 0, ...) and neg = (1, -1, 0, ...): the products sum
+        // to zero in the field, but the supports overlap
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let alloc = |values: [Fq; 2]| {
+            let coeff = values
+                .iter()
+                .copied()
+                .chain(std::iter::repeat(Fq::zero()))
+                .take(N)
+                .map(|value| FpVar::new_witness(cs.clone(), || Ok(value)).unwrap())
+                .collect();
+            PolyVar::new(coeff)
+        };
+        let forged = DualPolyVar {
+            pos: alloc([Fq::one(), Fq::one()]),
+            neg: alloc([Fq::one(), -Fq::one()]),
+        };
+        let sum = forged
+            .pos
+            .coeff()
+            .iter()
+            .zip(forged.neg.coeff().iter())
+            .fold(FpVar::zero(), |acc, (p, n)| acc + p * n);
+        assert_eq!(sum.value().unwrap(), Fq::zero());
+
+        forged.enforce_disjoint().unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_alloc_slice() {
+        let pos = [3u16, 0, 7, 0, 0, 1, 0, 0, 5, 0];
+        let neg = [0u16, 2, 0, 0, 4, 0, 6, 0, 0, 0];
+
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let dual_poly_var =
+            DualPolyVar::<Fq>::alloc_slice(cs.clone(), &pos, &neg, AllocationMode::Witness)
+                .unwrap();
+        assert_eq!(dual_poly_var.pos.coeff().len(), 10);
+        assert_eq!(dual_poly_var.neg.coeff().len(), 10);
+        for (e, &v) in dual_poly_var.pos.coeff().iter().zip(pos.iter()) {
+            assert_eq!(e.value().unwrap(), Fq::from(v));
+        }
+        for (e, &v) in dual_poly_var.neg.coeff().iter().zip(neg.iter()) {
+            assert_eq!(e.value().unwrap(), Fq::from(v));
+        }
+        assert_eq!(cs.num_constraints(), 10);
+        assert!(cs.is_satisfied().unwrap());
+
+        // overlapping supports
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        DualPolyVar::<Fq>::alloc_slice(cs.clone(), &pos, &pos, AllocationMode::Witness).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+
+        // mismatched lengths and unreduced coefficients
+        let mut unreduced = neg;
+        unreduced[1] = MODULUS;
+        for (pos, neg) in [(&pos[..], &neg[..9]), (&pos[..], &unreduced[..])].iter() {
+            let cs = ConstraintSystem::<Fq>::new_ref();
+            let res = DualPolyVar::<Fq>::alloc_slice(cs, pos, neg, AllocationMode::Witness);
+            assert!(matches!(res, Err(SynthesisError::Unsatisfiable)));
+        }
+    }
+
+    #[test]
+    fn test_enforce_recombines_to() {
+        let mut rng = test_rng();
+        let poly = Polynomial::rand(&mut rng);
+        let poly_ntt = *NTTPolynomial::from(&poly).coeff();
+        let mut wrong_ntt = poly_ntt;
+        wrong_ntt[0] = (wrong_ntt[0] + 1) % MODULUS;
+
+        for &(combined, is_correct) in [(&poly_ntt, true), (&wrong_ntt, false)].iter() {
+            let cs = ConstraintSystem::<Fq>::new_ref();
+            let const_vars: Vec<FpVar<Fq>> = power_of_q_field::<Fq>()
+                .iter()
+                .map(|x| FpVar::<Fq>::new_constant(cs.clone(), x))
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            let param_vars = ntt_param_var(cs.clone()).unwrap();
+            let modulus_var = modulus_var(cs.clone()).unwrap();
+
+            let dual_poly_var = DualPolyVar::<Fq>::alloc_vars(
+                cs.clone(),
+                &DualPolynomial::from(&poly),
+                AllocationMode::Witness,
+            )
+            .unwrap();
+            let dual_ntt_var =
+                DualNTTPolyVar::ntt_circuit(cs.clone(), &dual_poly_var, &const_vars, &param_vars)
+                    .unwrap();
+            let combined_var =
+                NTTPolyVar::<Fq>::alloc_slice(cs.clone(), combined, AllocationMode::Input).unwrap();
+
+            dual_ntt_var
+                .enforce_recombines_to(&combined_var, &modulus_var)
+                .unwrap();
+            assert_eq!(cs.is_satisfied().unwrap(), is_correct);
+        }
     }
 }
@@ -1,11 +1,15 @@
 mod arithmetics;
+mod commitment;
 mod dual_poly;
 mod misc;
+mod ntt_context;
 mod poly;
 mod range_proofs;
 
 pub use arithmetics::*;
+pub use commitment::*;
 pub use dual_poly::*;
 pub use misc::*;
+pub use ntt_context::NttContext;
 pub use poly::*;
 pub use range_proofs::*;
@@ -0,0 +1,107 @@
+use crate::ntt_param_var;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{fields::fp::FpVar, prelude::*};
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+use falcon_rust::power_of_q_field;
+use std::rc::Rc;
+
+/// The power of q wires and the NTT table wires
+type NttWires<'a, F> = (&'a [FpVar<F>], &'a [FpVar<F>]);
+
+/// A cache of the constant wires used by the NTT circuits, i.e.,
+/// the [q, 2*q^2, 4 * q^3, ..., 2^9 * q^10] wires and the forward
+/// NTT table, for one constraint system.
+///
+/// Use `wires` to obtain them: they are reused as long as the
+/// constraint system is the same, and re-allocated otherwise.
+#[derive(Clone)]
+pub struct NttContext<F: PrimeField> {
+    cs: ConstraintSystemRef<F>,
+    const_q_power_vars: Vec<FpVar<F>>,
+    param_vars: Vec<FpVar<F>>,
+}
+
+impl<F: PrimeField> NttContext<F> {
+    /// allocate the constant wires for cs
+    pub fn new(cs: ConstraintSystemRef<F>) -> Result<Self, SynthesisError> {
+        let const_q_power_vars = power_of_q_field::<F>()
+            .iter()
+            .map(|x| FpVar::<F>::new_constant(cs.clone(), x))
+            .collect::<Result<Vec<_>, _>>()?;
+        let param_vars = ntt_param_var(cs.clone())?;
+
+        Ok(Self {
+            cs,
+            const_q_power_vars,
+            param_vars,
+        })
+    }
+
+    /// Return the power of q wires and the NTT table wires for cs.
+    /// The cached wires are returned if cs is the constraint system
+    /// they were allocated for; otherwise they are re-allocated for cs.
+    pub fn wires(&mut self, cs: ConstraintSystemRef<F>) -> Result<NttWires<'_, F>, SynthesisError> {
+        if !same_cs(&self.cs, &cs) {
+            *self = Self::new(cs)?;
+        }
+        Ok((&self.const_q_power_vars, &self.param_vars))
+    }
+}
+
+/// Whether the two references point to the same constraint system
+fn same_cs<F: PrimeField>(a: &ConstraintSystemRef<F>, b: &ConstraintSystemRef<F>) -> bool {
+    match (a, b) {
+        (ConstraintSystemRef::CS(a), ConstraintSystemRef::CS(b)) => Rc::ptr_eq(a, b),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NTTPolyVar, PolyVar};
+    use ark_ed_on_bls12_381::fq::Fq;
+    use ark_relations::r1cs::ConstraintSystem;
+    use ark_std::test_rng;
+    use falcon_rust::{NTTPolynomial, Polynomial, N};
+
+    #[test]
+    fn test_ntt_context() {
+        let mut rng = test_rng();
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let mut ctx = NttContext::new(cs.clone()).unwrap();
+        // the wires are constants: no variables nor constraints
+        assert_eq!(cs.num_witness_variables(), 0);
+        assert_eq!(cs.num_constraints(), 0);
+
+        // two NTT circuits in the same constraint system
+        let mut ptrs = Vec::new();
+        for _ in 0..2 {
+            let poly = Polynomial::rand(&mut rng);
+            let poly_var =
+                PolyVar::<Fq>::alloc_vars(cs.clone(), &poly, AllocationMode::Witness).unwrap();
+            let (const_vars, param_vars) = ctx.wires(cs.clone()).unwrap();
+            ptrs.push((const_vars.as_ptr(), param_vars.as_ptr()));
+
+            let output_var =
+                NTTPolyVar::ntt_circuit(cs.clone(), &poly_var, const_vars, param_vars).unwrap();
+            let output = NTTPolynomial::from(&poly);
+            for i in 0..N {
+                assert_eq!(
+                    Fq::from(output.coeff()[i]),
+                    output_var.coeff()[i].value().unwrap()
+                )
+            }
+        }
+        assert!(cs.is_satisfied().unwrap());
+        // the cached wires are reused
+        assert_eq!(ptrs[0], ptrs[1]);
+
+        // a different constraint system gets new wires
+        let cs2 = ConstraintSystem::<Fq>::new_ref();
+        let (const_vars, _) = ctx.wires(cs2.clone()).unwrap();
+        assert_ne!(const_vars.as_ptr(), ptrs[0].0);
+        assert!(same_cs(&ctx.cs, &cs2));
+        assert!(!same_cs(&ctx.cs, &cs));
+    }
+}
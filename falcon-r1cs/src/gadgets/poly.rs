@@ -1,8 +1,8 @@
-use crate::mod_q;
+use crate::{assert_field_holds_ntt, mod_q, NttContext};
 use ark_ff::PrimeField;
 use ark_r1cs_std::{fields::fp::FpVar, prelude::*};
 use ark_relations::r1cs::{ConstraintSystemRef, Namespace, Result as ArkResult, SynthesisError};
-use falcon_rust::{NTTPolynomial, Polynomial, LOG_N, N};
+use falcon_rust::{NTTPolynomial, Polynomial, PolynomialVar, LOG_N, MODULUS, N};
 use std::ops::{Add, Mul};
 
 #[derive(Debug, Clone)]
@@ -49,17 +49,34 @@ impl<F: PrimeField> NTTPolyVar<F> {
         poly: &NTTPolynomial,
         mode: AllocationMode,
     ) -> Result<Self, SynthesisError> {
-        let ns = cs.into();
-        let cs = ns.cs();
-        let mut vec = Vec::new();
-        for &value in poly.coeff().iter() {
-            vec.push(FpVar::new_variable(
-                cs.clone(),
-                || Ok(F::from(value)),
-                mode,
-            )?);
+        Ok(Self(alloc_coeffs(cs, poly.coeff(), mode)?))
+    }
+
+    /// allocate variables for a slice of NTT coefficients of any length.
+    /// Returns `SynthesisError::Unsatisfiable` if a coefficient is not
+    /// smaller than MODULUS, without allocating any variable.
+    pub fn alloc_slice(
+        cs: impl Into<Namespace<F>>,
+        coeff: &[u16],
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        Ok(Self(alloc_reduced_coeffs(cs, coeff, mode)?))
+    }
+
+    /// allocate variables for the N NTT coefficients of a polynomial
+    /// given as a slice.
+    /// Returns `SynthesisError::Unsatisfiable` if the slice does not
+    /// have length N, or if a coefficient is not smaller than MODULUS,
+    /// rather than building a malformed circuit.
+    pub fn alloc_vars_from_slice(
+        cs: impl Into<Namespace<F>>,
+        coeff: &[u16],
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        if coeff.len() != N {
+            return Err(SynthesisError::Unsatisfiable);
         }
-        Ok(Self(vec))
+        Self::alloc_slice(cs, coeff, mode)
     }
 
     /// generate constraints proving that c = a * b without mod reduction
@@ -80,6 +97,19 @@ impl<F: PrimeField> NTTPolyVar<F> {
         Ok(())
     }
 
+    /// generate constraints proving that b = -a mod q, i.e., that
+    /// a[i] + b[i] is either 0 or q for all i.
+    /// Requires the coefficients of a and b to be smaller than q, as
+    /// enforced by `alloc_vars`, so that a[i] + b[i] < 2q.
+    /// Cost: 1 constraint per coefficient, for (a[i] + b[i]) * (a[i] + b[i] - q) = 0
+    pub fn enforce_negation(a: &Self, b: &Self, const_q: &FpVar<F>) -> ArkResult<()> {
+        for (ai, bi) in a.0.iter().zip(b.0.iter()) {
+            let sum = ai + bi;
+            sum.mul_equals(&(&sum - const_q), &FpVar::zero())?;
+        }
+        Ok(())
+    }
+
     pub fn mod_q(&self, cs: ConstraintSystemRef<F>, modulus_var: &FpVar<F>) -> Self {
         let res: Vec<FpVar<F>> = self
             .0
@@ -94,6 +124,22 @@ impl<F: PrimeField> NTTPolyVar<F> {
         &self.0
     }
 
+    /// Generate the NTT form of a coefficient-domain polynomial in-circuit,
+    /// instead of computing the NTT in the clear and allocating the result.
+    /// The constant wires for the NTT are built internally.
+    /// Cost 15360 constraints.
+    pub fn alloc_from_poly_var(
+        cs: ConstraintSystemRef<F>,
+        input: &PolyVar<F>,
+    ) -> Result<Self, SynthesisError> {
+        // the [q, 2*q^2, 4 * q^3, ..., 2^9 * q^10] constant wires
+        // and the NTT table
+        let mut ntt_context = NttContext::new(cs.clone())?;
+        let (const_q_power_vars, param_vars) = ntt_context.wires(cs.clone())?;
+
+        Self::ntt_circuit(cs, input, const_q_power_vars, param_vars)
+    }
+
     /// The circuit to convert a poly into its NTT form
     /// Cost 15360 constraints.
     /// Inputs:
@@ -132,6 +178,7 @@ impl<F: PrimeField> NTTPolyVar<F> {
         if input.coeff().len() != N {
             panic!("input length {} is not N", input.coeff().len())
         }
+        assert_field_holds_ntt::<F>();
         let mut output = input.coeff().to_vec();
 
         let mut t = N;
@@ -212,17 +259,18 @@ impl<F: PrimeField> PolyVar<F> {
         poly: &Polynomial,
         mode: AllocationMode,
     ) -> Result<Self, SynthesisError> {
-        let ns = cs.into();
-        let cs = ns.cs();
-        let mut vec = Vec::new();
-        for &value in poly.coeff().iter() {
-            vec.push(FpVar::new_variable(
-                cs.clone(),
-                || Ok(F::from(value)),
-                mode,
-            )?);
-        }
-        Ok(Self(vec))
+        Ok(Self(alloc_coeffs(cs, poly.coeff(), mode)?))
+    }
+
+    /// allocate variables for a slice of coefficients of any length.
+    /// Returns `SynthesisError::Unsatisfiable` if a coefficient is not
+    /// smaller than MODULUS, without allocating any variable.
+    pub fn alloc_slice(
+        cs: impl Into<Namespace<F>>,
+        coeff: &[u16],
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        Ok(Self(alloc_reduced_coeffs(cs, coeff, mode)?))
     }
 
     /// generate constraints proving that c = a * b without mod reduction
@@ -243,25 +291,129 @@ impl<F: PrimeField> PolyVar<F> {
         Ok(())
     }
 
+    /// generate constraints proving that the sum of the coefficients
+    /// equals expected; the sum is taken mod q if a modulus_var is given,
+    /// and over the integers otherwise.
+    /// The sum is a linear combination, so this costs a single constraint
+    /// without mod reduction.
+    pub fn enforce_coeff_sum(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        expected: &FpVar<F>,
+        modulus_var: Option<&FpVar<F>>,
+    ) -> ArkResult<()> {
+        let mut sum = FpVar::<F>::zero();
+        for e in self.0.iter() {
+            sum += e;
+        }
+        // the coefficients are smaller than q, so the sum of N of them
+        // does not wrap around the native field
+        let sum = match modulus_var {
+            Some(modulus_var) => mod_q(cs, &sum, modulus_var)?,
+            None => sum,
+        };
+        sum.enforce_equal(expected)
+    }
+
     /// Access the coefficients
     pub fn coeff(&self) -> &[FpVar<F>] {
         &self.0
     }
 }
 
+impl<F: PrimeField> PolynomialVar for PolyVar<F> {
+    type ConstraintSystem = ConstraintSystemRef<F>;
+    type Variable = FpVar<F>;
+    type NTTVar = NTTPolyVar<F>;
+    type Error = SynthesisError;
+
+    fn alloc(cs: &mut ConstraintSystemRef<F>, poly: &Polynomial) -> Result<Self, SynthesisError> {
+        Self::alloc_vars(cs.clone(), poly, AllocationMode::Witness)
+    }
+
+    fn coeff(&self) -> &[FpVar<F>] {
+        &self.0
+    }
+
+    fn ntt(&self, cs: &mut ConstraintSystemRef<F>) -> Result<NTTPolyVar<F>, SynthesisError> {
+        NTTPolyVar::alloc_from_poly_var(cs.clone(), self)
+    }
+}
+
+/// allocate a variable for each coefficient of the slice
+fn alloc_coeffs<F: PrimeField>(
+    cs: impl Into<Namespace<F>>,
+    coeff: &[u16],
+    mode: AllocationMode,
+) -> Result<Vec<FpVar<F>>, SynthesisError> {
+    let ns = cs.into();
+    let cs = ns.cs();
+    let mut vec = Vec::new();
+    for &value in coeff.iter() {
+        vec.push(FpVar::new_variable(
+            cs.clone(),
+            || Ok(F::from(value)),
+            mode,
+        )?);
+    }
+    Ok(vec)
+}
+
+/// allocate a variable for each coefficient of the slice, or return
+/// `SynthesisError::Unsatisfiable` if any of the coefficients is not
+/// smaller than MODULUS
+fn alloc_reduced_coeffs<F: PrimeField>(
+    cs: impl Into<Namespace<F>>,
+    coeff: &[u16],
+    mode: AllocationMode,
+) -> Result<Vec<FpVar<F>>, SynthesisError> {
+    if coeff.iter().any(|&value| value >= MODULUS) {
+        return Err(SynthesisError::Unsatisfiable);
+    }
+    alloc_coeffs(cs, coeff, mode)
+}
+
+/// Enforce that pk_ntt is the NTT form of pk_coeff, for circuits that take
+/// the public key in both forms, so that a prover cannot pick the two forms
+/// independently. The coefficients of pk_coeff are assumed in [0, q).
+/// Cost: the NTT circuit, plus N constraints.
+/// Inputs:
+/// - cs: constraint system
+/// - pk_coeff: the wires of the public key in coefficient form
+/// - pk_ntt: the wires of the public key in NTT form
+/// - const_vars: the [q, 2*q^2, 4 * q^3, ..., 2^9 * q^10] constant wires
+/// - param: the forward NTT table in wire format
+pub fn enforce_pk_consistency<F: PrimeField>(
+    cs: ConstraintSystemRef<F>,
+    pk_coeff: &PolyVar<F>,
+    pk_ntt: &NTTPolyVar<F>,
+    const_vars: &[FpVar<F>],
+    param: &[FpVar<F>],
+) -> ArkResult<()> {
+    if pk_ntt.coeff().len() != N {
+        panic!("pk_ntt length {} is not N", pk_ntt.coeff().len())
+    }
+    let ntt = NTTPolyVar::ntt_circuit(cs, pk_coeff, const_vars, param)?;
+    for (a, b) in ntt.coeff().iter().zip(pk_ntt.coeff().iter()) {
+        a.enforce_equal(b)?;
+    }
+    Ok(())
+}
+
 // TODO: more tests for the functions
 
 #[cfg(test)]
 mod tests {
-    use crate::ntt_param_var;
-
     use super::*;
+    use crate::{modulus_var, ntt_param_var};
     use ark_ed_on_bls12_381::fq::Fq;
     use ark_ff::Field;
     use ark_r1cs_std::{alloc::AllocVar, fields::fp::FpVar, R1CSVar};
     use ark_relations::r1cs::ConstraintSystem;
-    use ark_std::test_rng;
-    use falcon_rust::{NTTPolynomial, Polynomial, MODULUS};
+    use ark_std::{rand::Rng, test_rng};
+    use falcon_rust::{
+        alloc_and_ntt, power_of_q_field, KeyPair, NTTPolynomial, Polynomial, MODULUS,
+    };
 
     #[test]
     fn test_ntt_mul_circuit() {
@@ -271,14 +423,9 @@ mod tests {
             let cs = ConstraintSystem::<Fq>::new_ref();
             let param_vars = ntt_param_var(cs.clone()).unwrap();
             // the [q, 2*q^2, 4 * q^3, ..., 2^9 * q^10] constant wires
-            let const_power_q_vars: Vec<FpVar<Fq>> = (1..LOG_N + 2)
-                .map(|x| {
-                    FpVar::<Fq>::new_constant(
-                        cs.clone(),
-                        Fq::from(1 << (x - 1)) * Fq::from(MODULUS).pow(&[x as u64]),
-                    )
-                    .unwrap()
-                })
+            let const_power_q_vars: Vec<FpVar<Fq>> = power_of_q_field::<Fq>()
+                .iter()
+                .map(|x| FpVar::<Fq>::new_constant(cs.clone(), x).unwrap())
                 .collect();
             let poly = Polynomial::rand(&mut rng);
             let poly_var = PolyVar::<Fq>::alloc_vars(
@@ -314,4 +461,324 @@ mod tests {
 
         // assert!(false)
     }
+
+    #[cfg(feature = "falcon-512")]
+    #[test]
+    fn test_ntt_circuit_512() {
+        assert_eq!(N, 512);
+        assert_eq!(LOG_N, 9);
+
+        let mut rng = test_rng();
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let param_vars = ntt_param_var(cs.clone()).unwrap();
+        let const_power_q_vars: Vec<FpVar<Fq>> = power_of_q_field::<Fq>()
+            .iter()
+            .map(|x| FpVar::<Fq>::new_constant(cs.clone(), x).unwrap())
+            .collect();
+        assert_eq!(const_power_q_vars.len(), LOG_N + 1);
+
+        let poly = Polynomial::rand(&mut rng);
+        let poly_var = PolyVar::<Fq>::alloc_vars(
+            cs.clone(),
+            &poly,
+            ark_r1cs_std::alloc::AllocationMode::Witness,
+        )
+        .unwrap();
+        let output = NTTPolynomial::from(&poly);
+        let output_var =
+            NTTPolyVar::ntt_circuit(cs.clone(), &poly_var, &const_power_q_vars, &param_vars)
+                .unwrap();
+
+        assert_eq!(output_var.coeff().len(), 512);
+        for i in 0..N {
+            assert_eq!(
+                Fq::from(output.coeff()[i]),
+                output_var.coeff()[i].value().unwrap()
+            )
+        }
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_power_of_q_field() {
+        let powers = power_of_q_field::<Fq>();
+        assert_eq!(powers.len(), LOG_N + 1);
+        for (i, e) in powers.iter().enumerate() {
+            // the i-th element is 2^i * q^(i+1)
+            assert_eq!(
+                *e,
+                Fq::from(1u64 << i) * Fq::from(MODULUS).pow([i as u64 + 1])
+            );
+        }
+    }
+
+    #[test]
+    fn test_alloc_from_poly_var() {
+        let mut rng = test_rng();
+
+        for _ in 0..10 {
+            let cs = ConstraintSystem::<Fq>::new_ref();
+            let poly = Polynomial::rand(&mut rng);
+            let poly_ntt = NTTPolynomial::from(&poly);
+
+            let poly_var =
+                PolyVar::<Fq>::alloc_vars(cs.clone(), &poly, AllocationMode::Witness).unwrap();
+            let ntt_var = NTTPolyVar::alloc_from_poly_var(cs.clone(), &poly_var).unwrap();
+            let pre_transformed_var =
+                NTTPolyVar::<Fq>::alloc_vars(cs.clone(), &poly_ntt, AllocationMode::Input).unwrap();
+
+            assert_eq!(
+                ntt_var.coeff().value().unwrap(),
+                pre_transformed_var.coeff().value().unwrap()
+            );
+            ntt_var
+                .coeff()
+                .enforce_equal(pre_transformed_var.coeff())
+                .unwrap();
+            assert!(cs.is_satisfied().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_alloc_slice() {
+        let mut rng = test_rng();
+        let cs = ConstraintSystem::<Fq>::new_ref();
+
+        let coeff: Vec<u16> = (0..10).map(|_| rng.gen_range(0..MODULUS)).collect();
+        let poly_var =
+            PolyVar::<Fq>::alloc_slice(cs.clone(), &coeff, AllocationMode::Witness).unwrap();
+        let ntt_poly_var =
+            NTTPolyVar::<Fq>::alloc_slice(cs.clone(), &coeff, AllocationMode::Input).unwrap();
+
+        assert_eq!(poly_var.coeff().len(), 10);
+        assert_eq!(ntt_poly_var.coeff().len(), 10);
+        for (i, &e) in coeff.iter().enumerate() {
+            assert_eq!(Fq::from(e), poly_var.coeff()[i].value().unwrap());
+            assert_eq!(Fq::from(e), ntt_poly_var.coeff()[i].value().unwrap());
+        }
+
+        // an unreduced coefficient is an error, not a panic
+        let mut unreduced = coeff;
+        unreduced[9] = MODULUS;
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let res = PolyVar::<Fq>::alloc_slice(cs.clone(), &unreduced, AllocationMode::Witness);
+        assert!(matches!(res, Err(SynthesisError::Unsatisfiable)));
+        let res = NTTPolyVar::<Fq>::alloc_slice(cs.clone(), &unreduced, AllocationMode::Input);
+        assert!(matches!(res, Err(SynthesisError::Unsatisfiable)));
+        assert_eq!(cs.num_witness_variables(), 0);
+        assert_eq!(cs.num_instance_variables(), 1);
+    }
+
+    #[test]
+    fn test_enforce_coeff_sum() {
+        let coeff: Vec<u16> = (0..N).map(|i| (i * 97 % MODULUS as usize) as u16).collect();
+        let sum: u64 = coeff.iter().map(|&e| e as u64).sum();
+        assert!(sum >= MODULUS as u64);
+
+        for &reduce in [false, true].iter() {
+            let expected = if reduce { sum % MODULUS as u64 } else { sum };
+            for &(expected, is_correct) in [(expected, true), (expected + 1, false)].iter() {
+                let cs = ConstraintSystem::<Fq>::new_ref();
+                let modulus_var = modulus_var(cs.clone()).unwrap();
+                let poly_var = PolyVar::<Fq>::alloc_slice(
+                    cs.clone(),
+                    &coeff,
+                    ark_r1cs_std::alloc::AllocationMode::Witness,
+                )
+                .unwrap();
+                let expected_var =
+                    FpVar::<Fq>::new_input(cs.clone(), || Ok(Fq::from(expected))).unwrap();
+
+                let modulus_var = if reduce { Some(&modulus_var) } else { None };
+                poly_var
+                    .enforce_coeff_sum(cs.clone(), &expected_var, modulus_var)
+                    .unwrap();
+                assert_eq!(cs.is_satisfied().unwrap(), is_correct);
+            }
+        }
+    }
+
+    #[test]
+    fn test_alloc_vars_from_slice() {
+        let mut rng = test_rng();
+        let poly = NTTPolynomial::rand(&mut rng);
+
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let poly_var = NTTPolyVar::<Fq>::alloc_vars_from_slice(
+            cs.clone(),
+            poly.coeff(),
+            ark_r1cs_std::alloc::AllocationMode::Witness,
+        )
+        .unwrap();
+        for (e, &v) in poly_var.coeff().iter().zip(poly.coeff().iter()) {
+            assert_eq!(e.value().unwrap(), Fq::from(v));
+        }
+
+        let mut padded = poly.coeff().to_vec();
+        padded.push(0);
+        for coeff in [&poly.coeff()[..N - 1], &padded[..], &[]].iter() {
+            let cs = ConstraintSystem::<Fq>::new_ref();
+            let res = NTTPolyVar::<Fq>::alloc_vars_from_slice(
+                cs.clone(),
+                coeff,
+                ark_r1cs_std::alloc::AllocationMode::Witness,
+            );
+            assert!(matches!(res, Err(SynthesisError::Unsatisfiable)));
+            assert_eq!(cs.num_witness_variables(), 0);
+        }
+    }
+
+    #[test]
+    fn test_enforce_negation() {
+        let mut rng = test_rng();
+        let mut a = *NTTPolynomial::rand(&mut rng).coeff();
+        // 0 is its own negation
+        a[0] = 0;
+        let neg_a: Vec<u16> = a.iter().map(|&e| (MODULUS - e) % MODULUS).collect();
+        let mut not_neg_a = neg_a.clone();
+        not_neg_a[1] = (not_neg_a[1] + 1) % MODULUS;
+
+        for &(b, is_correct) in
+            [(&neg_a[..], true), (&not_neg_a[..], false), (&a[..], false)].iter()
+        {
+            let cs = ConstraintSystem::<Fq>::new_ref();
+            let modulus_var = modulus_var(cs.clone()).unwrap();
+            let a_var = NTTPolyVar::<Fq>::alloc_slice(
+                cs.clone(),
+                &a,
+                ark_r1cs_std::alloc::AllocationMode::Input,
+            )
+            .unwrap();
+            let b_var = NTTPolyVar::<Fq>::alloc_slice(
+                cs.clone(),
+                b,
+                ark_r1cs_std::alloc::AllocationMode::Input,
+            )
+            .unwrap();
+
+            let num_constraints = cs.num_constraints();
+            NTTPolyVar::enforce_negation(&a_var, &b_var, &modulus_var).unwrap();
+            assert_eq!(cs.num_constraints() - num_constraints, N);
+            assert_eq!(cs.is_satisfied().unwrap(), is_correct);
+        }
+    }
+
+    #[test]
+    fn test_enforce_pk_consistency() {
+        let pk = KeyPair::keygen_with_seed("test seed".as_ref()).public_key;
+        let pk_coeff = pk.unpack();
+        let pk_ntt = *pk.to_ntt().coeff();
+        let mut other_ntt = pk_ntt;
+        other_ntt[N - 1] = (other_ntt[N - 1] + 1) % MODULUS;
+
+        for &(ntt, is_correct) in [(&pk_ntt, true), (&other_ntt, false)].iter() {
+            let cs = ConstraintSystem::<Fq>::new_ref();
+            let param_vars = ntt_param_var(cs.clone()).unwrap();
+            let const_power_q_vars: Vec<FpVar<Fq>> = power_of_q_field::<Fq>()
+                .iter()
+                .map(|x| FpVar::<Fq>::new_constant(cs.clone(), x).unwrap())
+                .collect();
+            let pk_coeff_var = PolyVar::<Fq>::alloc_slice(
+                cs.clone(),
+                &pk_coeff,
+                ark_r1cs_std::alloc::AllocationMode::Witness,
+            )
+            .unwrap();
+            let pk_ntt_var = NTTPolyVar::<Fq>::alloc_slice(
+                cs.clone(),
+                ntt,
+                ark_r1cs_std::alloc::AllocationMode::Input,
+            )
+            .unwrap();
+
+            enforce_pk_consistency(
+                cs.clone(),
+                &pk_coeff_var,
+                &pk_ntt_var,
+                &const_power_q_vars,
+                &param_vars,
+            )
+            .unwrap();
+            assert_eq!(cs.is_satisfied().unwrap(), is_correct);
+        }
+    }
+
+    #[test]
+    fn test_polynomial_var() {
+        let mut rng = test_rng();
+        let mut cs = ConstraintSystem::<Fq>::new_ref();
+        let poly = Polynomial::rand(&mut rng);
+        let output = NTTPolynomial::from(&poly);
+
+        let (poly_var, ntt_var) = alloc_and_ntt::<PolyVar<Fq>>(&mut cs, &poly).unwrap();
+        for i in 0..N {
+            assert_eq!(
+                Fq::from(poly.coeff()[i]),
+                poly_var.coeff()[i].value().unwrap()
+            );
+            assert_eq!(
+                Fq::from(output.coeff()[i]),
+                ntt_var.coeff()[i].value().unwrap()
+            );
+        }
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_ntt_circuit_cost_breakdown() {
+        let mut rng = test_rng();
+        let poly = Polynomial::rand(&mut rng);
+
+        // the full NTT circuit
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let poly_var = PolyVar::<Fq>::alloc_vars(
+            cs.clone(),
+            &poly,
+            ark_r1cs_std::alloc::AllocationMode::Witness,
+        )
+        .unwrap();
+        let num_constraints = cs.num_constraints();
+        let num_witness_variables = cs.num_witness_variables();
+        NTTPolyVar::alloc_from_poly_var(cs.clone(), &poly_var).unwrap();
+        let ntt_num_constraints = cs.num_constraints() - num_constraints;
+        let ntt_num_witness_variables = cs.num_witness_variables() - num_witness_variables;
+
+        // the butterflies and the reduction, separately
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let param_vars = ntt_param_var(cs.clone()).unwrap();
+        let const_power_q_vars: Vec<FpVar<Fq>> = power_of_q_field::<Fq>()
+            .iter()
+            .map(|x| FpVar::<Fq>::new_constant(cs.clone(), x).unwrap())
+            .collect();
+        let poly_var = PolyVar::<Fq>::alloc_vars(
+            cs.clone(),
+            &poly,
+            ark_r1cs_std::alloc::AllocationMode::Witness,
+        )
+        .unwrap();
+        let num_constraints = cs.num_constraints();
+        let num_witness_variables = cs.num_witness_variables();
+        let output_var =
+            NTTPolyVar::ntt_circuit_defer_range_check(&poly_var, &const_power_q_vars, &param_vars)
+                .unwrap();
+        // the butterflies are native operations with constant twiddles
+        assert_eq!(cs.num_constraints(), num_constraints);
+        assert_eq!(cs.num_witness_variables(), num_witness_variables);
+
+        let reduced = output_var.mod_q(cs.clone(), &const_power_q_vars[0]);
+        assert_eq!(cs.num_constraints() - num_constraints, ntt_num_constraints);
+        assert_eq!(
+            cs.num_witness_variables() - num_witness_variables,
+            ntt_num_witness_variables
+        );
+
+        let output = NTTPolynomial::from(&poly);
+        for i in 0..N {
+            assert_eq!(
+                Fq::from(output.coeff()[i]),
+                reduced.coeff()[i].value().unwrap()
+            )
+        }
+        assert!(cs.is_satisfied().unwrap());
+    }
 }
@@ -37,12 +37,21 @@ pub fn enforce_less_than_1024<F: PrimeField>(
 }
 
 /// Constraint that the witness of a is smaller than 12289
-/// Cost: 28 constraints.
+/// Cost: 29 constraints.
 /// (This improves the range proof of 1264 constraints as in Arkworks.)
 pub(crate) fn enforce_less_than_q<F: PrimeField>(
     cs: ConstraintSystemRef<F>,
     a: &FpVar<F>,
 ) -> Result<(), SynthesisError> {
+    less_than_q_bits(cs, a).map(|_| ())
+}
+
+/// Same as `enforce_less_than_q`, also returning the 14 bits of a,
+/// least significant first
+pub(crate) fn less_than_q_bits<F: PrimeField>(
+    cs: ConstraintSystemRef<F>,
+    a: &FpVar<F>,
+) -> Result<Vec<Boolean<F>>, SynthesisError> {
     // if !cs.is_in_setup_mode(){
     // println!("< norm 12289 satisfied? {:?}", cs.is_satisfied());
     // }
@@ -90,11 +99,11 @@ pub(crate) fn enforce_less_than_q<F: PrimeField>(
     // if !cs.is_in_setup_mode(){
     // println!("< norm 12289 satisfied? {:?}", cs.is_satisfied());
     // }
-    Ok(())
+    Ok(a_bit_vars)
 }
 
 /// Constraint that the witness of a is smaller than 34034726
-/// Cost: 47 constraints.
+/// Cost: 52 constraints.
 /// (This improves the range proof of 1264 constraints as in Arkworks.)    
 #[cfg(feature = "falcon-512")]
 fn enforce_less_than_norm_bound_512<F: PrimeField>(
@@ -284,7 +293,7 @@ pub fn enforce_less_than_norm_bound<F: PrimeField>(
 }
 
 /// Return a variable indicating if the input is less than 6144 or not
-/// Cost: 18 constraints.
+/// Cost: 17 constraints.
 /// (This improves the range proof of 1264 constraints as in Arkworks.)
 pub(crate) fn is_less_than_6144<F: PrimeField>(
     cs: ConstraintSystemRef<F>,
@@ -332,13 +341,58 @@ pub(crate) fn is_less_than_6144<F: PrimeField>(
     res
 }
 
+/// Enforce that every input in [0, 12289), lifted to [-6144, 6144],
+/// has absolute value at most bound: the infinity norm bound that the
+/// plonk circuit checks with bound = 765.
+/// Panics if bound >= 6144.
+/// Cost: per input, 17 for the lifting, 1 for the selection and k + 1
+/// for the range proof of bound - |a|, where 2^k > bound.
+pub fn enforce_infinity_norm_bound<F: PrimeField>(
+    cs: ConstraintSystemRef<F>,
+    coeffs: &[FpVar<F>],
+    bound: u16,
+) -> Result<(), SynthesisError> {
+    assert!(bound < 6144, "Invalid input: {}", bound);
+
+    // the number of bits of bound
+    let k = 16 - bound.leading_zeros() as usize;
+    let modulus_var = modulus_var(cs.clone())?;
+    let bound_var = FpVar::<F>::Constant(F::from(bound));
+
+    for e in coeffs.iter() {
+        let abs = FpVar::<F>::conditionally_select(
+            &is_less_than_6144(cs.clone(), e)?,
+            e,
+            &(&modulus_var - e),
+        )?;
+
+        // |a| <= bound iff bound - |a| fits in k bits; otherwise it
+        // wraps around to a field element with more than k bits
+        let diff = &bound_var - &abs;
+        let diff_val = if cs.is_in_setup_mode() {
+            F::one()
+        } else {
+            diff.value()?
+        };
+        let diff_bit_vars = diff_val
+            .into_repr()
+            .to_bits_le()
+            .iter()
+            .take(k.max(1))
+            .map(|x| Boolean::new_witness(cs.clone(), || Ok(x)))
+            .collect::<Result<Vec<_>, _>>()?;
+        enforce_decompose(&diff, diff_bit_vars.as_ref())?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use ark_ed_on_bls12_381::fq::Fq;
     use ark_relations::r1cs::ConstraintSystem;
     use ark_std::{rand::Rng, test_rng};
-    use falcon_rust::{MODULUS, SIG_L2_BOUND};
+    use falcon_rust::{Polynomial, MODULUS, SIG_L2_BOUND};
 
     macro_rules! test_range_proof_mod_q {
         ($value: expr, $satisfied: expr) => {
@@ -482,6 +536,12 @@ mod tests {
             test_range_proof_norm_bound!(t, t < SIG_L2_BOUND);
         }
 
+        // norms of polynomials sampled close to the bound
+        for _ in 0..10 {
+            let poly = Polynomial::rand_with_l2_norm_below(&mut rng, SIG_L2_BOUND);
+            test_range_proof_norm_bound!(poly.l2_norm(), true);
+        }
+
         // the following code prints out the
         // cost for arkworks native range proof
         // {
@@ -647,4 +707,58 @@ mod tests {
         // }
         // assert!(false)
     }
+
+    macro_rules! enforce_infinity_norm_bound {
+        ($value: expr, $bound: expr, $satisfied: expr) => {
+            let cs = ConstraintSystem::<Fq>::new_ref();
+            let a = Fq::from($value);
+            let a_var = FpVar::<Fq>::new_witness(cs.clone(), || Ok(a)).unwrap();
+
+            enforce_infinity_norm_bound(cs.clone(), &[a_var], $bound).unwrap();
+            assert_eq!(cs.is_satisfied().unwrap(), $satisfied);
+        };
+    }
+
+    #[test]
+    fn test_enforce_infinity_norm_bound() {
+        // =======================
+        // straddling the bound
+        // =======================
+        enforce_infinity_norm_bound!(0, 765, true);
+        enforce_infinity_norm_bound!(765, 765, true);
+        enforce_infinity_norm_bound!(766, 765, false);
+        enforce_infinity_norm_bound!(MODULUS - 765, 765, true);
+        enforce_infinity_norm_bound!(MODULUS - 766, 765, false);
+        // the largest magnitudes
+        enforce_infinity_norm_bound!(6144, 765, false);
+        enforce_infinity_norm_bound!(6145, 765, false);
+        enforce_infinity_norm_bound!(6144, 6143, false);
+        enforce_infinity_norm_bound!(6143, 6143, true);
+        // a power of two, and 0
+        enforce_infinity_norm_bound!(1024, 1024, true);
+        enforce_infinity_norm_bound!(1025, 1024, false);
+        enforce_infinity_norm_bound!(0, 0, true);
+        enforce_infinity_norm_bound!(1, 0, false);
+        enforce_infinity_norm_bound!(MODULUS - 1, 0, false);
+
+        // =======================
+        // a whole polynomial
+        // =======================
+        let mut rng = test_rng();
+        for _ in 0..10 {
+            let poly = Polynomial::rand_with_l2_norm_below(&mut rng, SIG_L2_BOUND);
+            let norm = poly.infinity_norm();
+            for &(bound, satisfied) in [(norm, true), (norm - 1, false)].iter() {
+                let cs = ConstraintSystem::<Fq>::new_ref();
+                let poly_vars = poly
+                    .coeff()
+                    .iter()
+                    .map(|&e| FpVar::<Fq>::new_witness(cs.clone(), || Ok(Fq::from(e))))
+                    .collect::<Result<Vec<_>, _>>()
+                    .unwrap();
+                enforce_infinity_norm_bound(cs.clone(), &poly_vars, bound as u16).unwrap();
+                assert_eq!(cs.is_satisfied().unwrap(), satisfied);
+            }
+        }
+    }
 }
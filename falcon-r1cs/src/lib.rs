@@ -1,8 +1,12 @@
 mod circuits;
+#[cfg(feature = "r1cs-export")]
+mod export;
 mod gadgets;
 
 pub use circuits::{
-    FalconDualNTTVerificationCircuit, FalconNTTVerificationCircuit,
-    FalconSchoolBookVerificationCircuit,
+    FalconDualNTTVerificationCircuit, FalconMultiMessageCircuit, FalconNTTVerificationCircuit,
+    FalconPublicInputs, FalconSchoolBookVerificationCircuit,
 };
+#[cfg(feature = "r1cs-export")]
+pub use export::{circuit_matrices, export_r1cs, import_r1cs};
 pub use gadgets::*;
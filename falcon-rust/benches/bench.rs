@@ -2,7 +2,9 @@
 extern crate criterion;
 
 use criterion::Criterion;
-use falcon_rust::{KeyPair, NTTPolynomial, Polynomial};
+#[cfg(feature = "parallel")]
+use falcon_rust::verify_batch_rust_parallel;
+use falcon_rust::{verify_batch_rust, KeyPair, NTTPolynomial, Polynomial, PublicKey, Signature};
 use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
 
 criterion_main!(bench);
@@ -19,11 +21,19 @@ fn bench_ntt(c: &mut Criterion) {
 
     let mut bench_group = c.benchmark_group("NTT transforms");
     bench_group.sample_size(100);
+
+    // a single transform, so that changes to the butterfly loop are not
+    // diluted by the iteration over the inputs
+    let poly = polys[0];
+    bench_group.bench_function("single forward ntt", move |b| {
+        b.iter(|| NTTPolynomial::from(criterion::black_box(&poly)));
+    });
+
     let bench_str = format!("{} of forward ntt", num_tests);
     bench_group.bench_function(bench_str, move |b| {
         b.iter(|| {
-            for i in 0..num_tests {
-                let _: NTTPolynomial = (&polys[i]).into();
+            for poly in polys.iter() {
+                let _: NTTPolynomial = poly.into();
             }
         });
     });
@@ -32,8 +42,8 @@ fn bench_ntt(c: &mut Criterion) {
     let bench_str = format!("{} of inverse NTT transform", num_tests);
     bench_group.bench_function(bench_str, move |b| {
         b.iter(|| {
-            for i in 0..num_tests {
-                let _: Polynomial = (&poly_ntts_clone[i]).into();
+            for poly_ntt in poly_ntts_clone.iter() {
+                let _: Polynomial = poly_ntt.into();
             }
         });
     });
@@ -43,8 +53,8 @@ fn bench_ntt(c: &mut Criterion) {
     let bench_str = format!("{} of ntt additions", num_tests);
     bench_group.bench_function(bench_str, move |b| {
         b.iter(|| {
-            for i in 0..num_tests {
-                let _ = poly_ntts_clone[i].clone() + another_poly_ntts_clone[i].clone();
+            for (a, b) in poly_ntts_clone.iter().zip(another_poly_ntts_clone.iter()) {
+                let _ = a + b;
             }
         });
     });
@@ -52,8 +62,8 @@ fn bench_ntt(c: &mut Criterion) {
     let bench_str = format!("{} of ntt multiplications", num_tests);
     bench_group.bench_function(bench_str, move |b| {
         b.iter(|| {
-            for i in 0..num_tests {
-                let _ = poly_ntts[i].clone() * another_poly_ntts[i].clone();
+            for (a, b) in poly_ntts.iter().zip(another_poly_ntts.iter()) {
+                let _ = a * b;
             }
         });
     });
@@ -101,6 +111,7 @@ fn bench_falcon(c: &mut Criterion) {
             });
         });
     }
+    #[cfg(feature = "ffi")]
     {
         let keypair = KeyPair::keygen();
         let message = "testing message";
@@ -132,4 +143,62 @@ fn bench_falcon(c: &mut Criterion) {
             });
         });
     }
+    {
+        let keypair = KeyPair::keygen();
+        let message = "testing message";
+        let sig = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), message.as_ref());
+        let bench_str = format!("{} verifications in rust, cold public key", num_tests);
+        bench_group.bench_function(bench_str, move |b| {
+            b.iter(|| {
+                for _ in 0..num_tests {
+                    // unpack and NTT the public key on every call
+                    let pk = keypair.public_key.prepare();
+                    assert!(pk.verify_rust(message.as_ref(), &sig));
+                }
+            });
+        });
+    }
+
+    {
+        let keypair = KeyPair::keygen();
+        let message = "testing message";
+        let sig = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), message.as_ref());
+        let pk = keypair.public_key.prepare();
+        let bench_str = format!("{} verifications in rust, warm public key", num_tests);
+        bench_group.bench_function(bench_str, move |b| {
+            b.iter(|| {
+                for _ in 0..num_tests {
+                    assert!(pk.verify_rust(message.as_ref(), &sig));
+                }
+            });
+        });
+    }
+
+    {
+        let batch_size = 100;
+        let keypair = KeyPair::keygen();
+        let message = "testing message";
+        let sigs: Vec<Signature> = (0..batch_size)
+            .map(|_| keypair.secret_key.sign(message.as_ref()))
+            .collect();
+        let batch: Vec<(&PublicKey, &[u8], &Signature)> = sigs
+            .iter()
+            .map(|sig| (&keypair.public_key, message.as_ref(), sig))
+            .collect();
+        let bench_str = format!("batch of {} verifications in rust", batch_size);
+        bench_group.bench_function(bench_str, |b| {
+            b.iter(|| assert!(verify_batch_rust(&batch).iter().all(|&x| x)));
+        });
+        #[cfg(feature = "parallel")]
+        {
+            let bench_str = format!("batch of {} verifications in rust, parallel", batch_size);
+            bench_group.bench_function(bench_str, |b| {
+                b.iter(|| assert!(verify_batch_rust_parallel(&batch).iter().all(|&x| x)));
+            });
+        }
+    }
 }
@@ -0,0 +1,75 @@
+//! A minimal command line tool for falcon signatures.
+//!
+//!     sign_verify_cli keygen <pk file> <sk file>
+//!     sign_verify_cli sign <sk file> <message file> <signature file>
+//!     sign_verify_cli verify <pk file> <signature file> <message file>
+//!
+//! `verify` exits with 0 if the signature is valid, and 1 otherwise.
+//! Other errors exit with 2.
+use falcon_rust::*;
+use std::{env, fs, process};
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(|x| x.as_str()).collect();
+
+    let res = match args.as_slice() {
+        ["keygen", pk_file, sk_file] => keygen(pk_file, sk_file),
+        ["sign", sk_file, msg_file, sig_file] => sign(sk_file, msg_file, sig_file),
+        ["verify", pk_file, sig_file, msg_file] => match verify(pk_file, sig_file, msg_file) {
+            Ok(true) => {
+                println!("valid signature");
+                Ok(())
+            },
+            Ok(false) => {
+                println!("invalid signature");
+                process::exit(1)
+            },
+            Err(e) => Err(e),
+        },
+        _ => {
+            eprintln!(
+                "usage:\n  \
+                 sign_verify_cli keygen <pk file> <sk file>\n  \
+                 sign_verify_cli sign <sk file> <message file> <signature file>\n  \
+                 sign_verify_cli verify <pk file> <signature file> <message file>"
+            );
+            process::exit(2)
+        },
+    };
+
+    if let Err(e) = res {
+        eprintln!("error: {}", e);
+        process::exit(2)
+    }
+}
+
+fn read(file: &str) -> Result<Vec<u8>, String> {
+    fs::read(file).map_err(|e| format!("cannot read {}: {}", file, e))
+}
+
+fn write(file: &str, bytes: &[u8]) -> Result<(), String> {
+    fs::write(file, bytes).map_err(|e| format!("cannot write {}: {}", file, e))
+}
+
+fn keygen(pk_file: &str, sk_file: &str) -> Result<(), String> {
+    let keypair = KeyPair::keygen();
+    write(pk_file, keypair.public_key.as_bytes())?;
+    write(sk_file, keypair.secret_key.as_bytes())
+}
+
+fn sign(sk_file: &str, msg_file: &str, sig_file: &str) -> Result<(), String> {
+    let sk = SecretKey::from_bytes(&read(sk_file)?).map_err(|e| e.to_string())?;
+    let sig = sk.sign(&read(msg_file)?);
+    write(sig_file, sig.as_bytes())
+}
+
+fn verify(pk_file: &str, sig_file: &str, msg_file: &str) -> Result<bool, String> {
+    let pk = PublicKey::from_bytes(&read(pk_file)?).map_err(|e| e.to_string())?;
+    // a signature that does not parse is reported as invalid
+    let sig = match Signature::from_bytes(&read(sig_file)?) {
+        Ok(sig) => sig,
+        Err(_) => return Ok(false),
+    };
+    Ok(pk.verify_rust(&read(msg_file)?, &sig))
+}
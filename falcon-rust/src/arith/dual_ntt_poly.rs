@@ -1,4 +1,5 @@
-use crate::{DualPolynomial, NTTPolynomial, MODULUS, N};
+use crate::{DualPolynomial, NTTPolynomial, Polynomial, MODULUS, N};
+use std::ops::{Add, Sub};
 
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub struct DualNTTPolynomial {
@@ -26,7 +27,67 @@ impl From<&DualNTTPolynomial> for NTTPolynomial {
     }
 }
 
+impl From<&NTTPolynomial> for DualNTTPolynomial {
+    fn from(poly: &NTTPolynomial) -> Self {
+        Self::from_ntt(poly)
+    }
+}
+
+// The dual NTT form is not closed under addition: the sum of the
+// positive parts is not the positive part of the sum. So the operands
+// are recombined, added in the NTT domain, and the result is re-split.
+impl Add for DualNTTPolynomial {
+    type Output = Self;
+    fn add(self, other: Self) -> <Self as Add<Self>>::Output {
+        Self::from_ntt(&(self.to_ntt() + other.to_ntt()))
+    }
+}
+
+impl Sub for DualNTTPolynomial {
+    type Output = Self;
+    fn sub(self, other: Self) -> <Self as Sub<Self>>::Output {
+        Self::from_ntt(&(self.to_ntt() - other.to_ntt()))
+    }
+}
+
+impl From<&DualNTTPolynomial> for DualPolynomial {
+    fn from(dual_poly: &DualNTTPolynomial) -> Self {
+        dual_poly.to_dual_poly()
+    }
+}
+
 impl DualNTTPolynomial {
+    /// Build the dual NTT form directly from an NTT form polynomial:
+    /// the input is converted back to the coefficient domain, split into
+    /// its positive and negative parts, and both parts are converted into
+    /// the NTT domain.
+    pub fn from_ntt(poly: &NTTPolynomial) -> Self {
+        let poly: Polynomial = poly.into();
+        (&DualPolynomial::from(&poly)).into()
+    }
+
+    /// Recombine the two halves into the NTT form of pos - neg
+    pub fn to_ntt(&self) -> NTTPolynomial {
+        self.into()
+    }
+
+    /// Convert back to the coefficient domain via an inverse NTT of
+    /// each half
+    pub fn to_dual_poly(&self) -> DualPolynomial {
+        DualPolynomial {
+            pos: (&self.pos).into(),
+            neg: (&self.neg).into(),
+        }
+    }
+
+    /// square of l2 norm of the polynomial.
+    /// The norm is only meaningful in the coefficient domain, so this
+    /// is the norm of `self.to_dual_poly()`, not a norm of the NTT
+    /// coefficients.
+    pub fn l2_norm(&self) -> u64 {
+        self.to_dual_poly().l2_norm()
+    }
+
     /// Multiply self by a Polynomial
     pub fn mul_by_poly(&self, other: &NTTPolynomial) -> Self {
         Self {
@@ -38,7 +99,7 @@ impl DualNTTPolynomial {
 
 #[cfg(test)]
 mod test {
-    use crate::Polynomial;
+    use crate::MODULUS_MINUS_1_OVER_TWO;
 
     use super::*;
     use rand_chacha::ChaCha20Rng;
@@ -55,4 +116,58 @@ mod test {
             assert_eq!(poly_ntt, poly_ntt_rec)
         }
     }
+
+    #[test]
+    fn test_dual_ntt_poly_l2_norm() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        for _ in 0..100 {
+            let mut poly = Polynomial::rand(&mut rng);
+            // (q-1)/2 is lifted differently by the two representations
+            for e in poly.0.iter_mut() {
+                if *e == MODULUS_MINUS_1_OVER_TWO {
+                    *e = 0
+                }
+            }
+            let dual_poly = DualPolynomial::from(&poly);
+            let dual_ntt_poly = DualNTTPolynomial::from(&dual_poly);
+
+            assert_eq!(dual_ntt_poly.to_dual_poly(), dual_poly);
+            assert_eq!(DualPolynomial::from(&dual_ntt_poly), dual_poly);
+            assert_eq!(dual_ntt_poly.l2_norm(), dual_poly.l2_norm());
+
+            // recombine and compare with the coefficient-domain norm
+            let poly_rec = Polynomial::from(&NTTPolynomial::from(&dual_ntt_poly));
+            assert_eq!(dual_ntt_poly.l2_norm(), poly_rec.l2_norm());
+        }
+    }
+
+    #[test]
+    fn test_dual_ntt_poly_from_ntt() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        for _ in 0..100 {
+            let poly = Polynomial::rand(&mut rng);
+            let poly_ntt = NTTPolynomial::from(&poly);
+            let dual_ntt_poly = DualNTTPolynomial::from(&DualPolynomial::from(&poly));
+
+            assert_eq!(DualNTTPolynomial::from_ntt(&poly_ntt), dual_ntt_poly);
+            assert_eq!(DualNTTPolynomial::from(&poly_ntt), dual_ntt_poly);
+        }
+    }
+
+    #[test]
+    fn test_dual_ntt_poly_add_sub() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        for _ in 0..100 {
+            let a = NTTPolynomial::rand(&mut rng);
+            let b = NTTPolynomial::rand(&mut rng);
+            let dual_a = DualNTTPolynomial::from_ntt(&a);
+            let dual_b = DualNTTPolynomial::from_ntt(&b);
+            assert_eq!(dual_a.to_ntt(), a);
+
+            assert_eq!(dual_a + dual_b, DualNTTPolynomial::from_ntt(&(a + b)));
+            assert_eq!(dual_a - dual_b, DualNTTPolynomial::from_ntt(&(a - b)));
+            assert_eq!((dual_a + dual_b).to_ntt(), a + b);
+            assert_eq!((dual_a - dual_b).to_ntt(), a - b);
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{Polynomial, MODULUS, MODULUS_MINUS_1_OVER_TWO, N};
+use crate::{DualNTTPolynomial, Polynomial, MODULUS, MODULUS_MINUS_1_OVER_TWO, N};
 
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub struct DualPolynomial {
@@ -8,16 +8,7 @@ pub struct DualPolynomial {
 
 impl From<&Polynomial> for DualPolynomial {
     fn from(poly: &Polynomial) -> Self {
-        let mut res = Self::default();
-        for i in 0..N {
-            if poly.coeff()[i] < MODULUS_MINUS_1_OVER_TWO {
-                res.pos.0[i] = poly.coeff()[i]
-            } else {
-                res.neg.0[i] = MODULUS - poly.coeff()[i]
-            }
-        }
-
-        res
+        Self::from_poly_with_threshold(poly, MODULUS_MINUS_1_OVER_TWO)
     }
 }
 
@@ -33,11 +24,67 @@ impl From<&DualPolynomial> for Polynomial {
 }
 
 impl DualPolynomial {
+    /// Split a polynomial at a custom threshold: coefficients smaller
+    /// than threshold go to pos, and the others are lifted to
+    /// MODULUS - coefficient in neg.
+    /// The `From<&Polynomial>` conversion uses (MODULUS - 1) / 2.
+    /// Panics if threshold is not in [1, MODULUS].
+    pub fn from_poly_with_threshold(poly: &Polynomial, threshold: u16) -> Self {
+        assert!(
+            (1..=MODULUS).contains(&threshold),
+            "Invalid input: {}",
+            threshold
+        );
+
+        let mut res = Self::default();
+        for i in 0..N {
+            if poly.coeff()[i] < threshold {
+                res.pos.0[i] = poly.coeff()[i]
+            } else {
+                res.neg.0[i] = MODULUS - poly.coeff()[i]
+            }
+        }
+
+        res
+    }
+
     /// square of l2 norm of the polynomial
     pub fn l2_norm(&self) -> u64 {
         self.pos.l2_norm() + self.neg.l2_norm()
     }
 
+    /// The signed coefficients pos - neg
+    pub fn to_signed(&self) -> [i16; N] {
+        let mut res = [0i16; N];
+        for (i, e) in res.iter_mut().enumerate() {
+            *e = self.pos.coeff()[i] as i16 - self.neg.coeff()[i] as i16;
+        }
+        res
+    }
+
+    /// Build a dual polynomial from signed coefficients:
+    /// positive coefficients go to pos, and the magnitudes of
+    /// negative coefficients go to neg.
+    /// Panics if any coefficient is not in (-MODULUS, MODULUS).
+    pub fn from_signed(coeff: &[i16; N]) -> Self {
+        let mut res = Self::default();
+        for (i, &e) in coeff.iter().enumerate() {
+            assert!((e as i32).abs() < MODULUS as i32, "Invalid input: {}", e);
+            if e >= 0 {
+                res.pos.0[i] = e as u16
+            } else {
+                res.neg.0[i] = (-e) as u16
+            }
+        }
+        res
+    }
+
+    /// Convert both halves into the NTT domain; the same as
+    /// `DualNTTPolynomial::from(self)`
+    pub fn to_dual_ntt(&self) -> DualNTTPolynomial {
+        self.into()
+    }
+
     /// Multiply self by a Polynomial
     pub fn mul_by_poly(&self, other: &Polynomial) -> Self {
         Self {
@@ -51,7 +98,7 @@ impl DualPolynomial {
 mod test {
     use super::*;
     use rand_chacha::ChaCha20Rng;
-    use rand_core::SeedableRng;
+    use rand_core::{RngCore, SeedableRng};
     #[test]
     fn test_dual_poly_conversion() {
         let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
@@ -62,4 +109,58 @@ mod test {
             assert_eq!(poly, poly_rec)
         }
     }
+
+    #[test]
+    fn test_dual_poly_signed() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        for _ in 0..100 {
+            let mut signed = [0i16; N];
+            for e in signed.iter_mut() {
+                *e = (rng.next_u32() % MODULUS_MINUS_1_OVER_TWO as u32) as i16;
+                if rng.next_u32() & 1 == 1 {
+                    *e = -*e
+                }
+            }
+            let dual_poly = DualPolynomial::from_signed(&signed);
+            assert_eq!(dual_poly.to_signed(), signed);
+
+            // consistent with the conversion from a Polynomial
+            let poly = Polynomial::from(&dual_poly);
+            assert_eq!(DualPolynomial::from(&poly), dual_poly);
+            for (&c, &s) in poly.coeff().iter().zip(signed.iter()) {
+                assert_eq!(c as i32, (s as i32).rem_euclid(MODULUS as i32));
+            }
+        }
+    }
+
+    #[test]
+    fn test_dual_poly_threshold() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        for _ in 0..100 {
+            let poly = Polynomial::rand(&mut rng);
+            let dual_poly =
+                DualPolynomial::from_poly_with_threshold(&poly, MODULUS_MINUS_1_OVER_TWO);
+            assert_eq!(dual_poly, DualPolynomial::from(&poly));
+            assert_eq!(Polynomial::from(&dual_poly), poly);
+
+            for &threshold in [1, 100, MODULUS / 3, MODULUS - 1, MODULUS].iter() {
+                let dual_poly = DualPolynomial::from_poly_with_threshold(&poly, threshold);
+                assert_eq!(Polynomial::from(&dual_poly), poly);
+                for i in 0..N {
+                    assert!(dual_poly.pos.coeff()[i] < threshold);
+                    assert!(dual_poly.neg.coeff()[i] <= MODULUS - threshold);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_dual_ntt() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        for _ in 0..10 {
+            let dual_poly = DualPolynomial::from(&Polynomial::rand(&mut rng));
+            assert_eq!(dual_poly.to_dual_ntt(), DualNTTPolynomial::from(&dual_poly));
+            assert_eq!(dual_poly.to_dual_ntt().to_dual_poly(), dual_poly);
+        }
+    }
 }
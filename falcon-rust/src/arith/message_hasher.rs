@@ -0,0 +1,188 @@
+use super::Polynomial;
+use crate::{shake256_context, MODULUS, N};
+
+// the SHAKE256 block size, in bytes; even, so that no 16-bit sample
+// straddles two blocks
+const SHAKE256_RATE: usize = 136;
+
+/// The number of 16-bit samples that `from_hash_of_message_with_limit`
+/// extracts up front by default.
+///
+/// A sample is rejected with probability 1 - 61445/65536 ~ 0.0624, so
+/// hashing takes on average N / 0.9376 samples. Running out of
+/// N + N/4 samples needs more than N/4 rejections, more than three
+/// times the expected count; by a Chernoff bound this happens with
+/// probability below 2^-98 for N = 512 and 2^-195 for N = 1024.
+pub const HASH_TO_POINT_SAMPLES: usize = N + N / 4;
+
+/// Incremental hashing of a (nonce, message) pair into a polynomial,
+/// for messages that are not available as a single slice.
+///
+/// `MessageHasher::new(nonce)` followed by `update`s with the chunks of
+/// the message and `finalize` gives the same polynomial as
+/// `Polynomial::from_hash_of_message(message, nonce)`.
+#[derive(Debug, Clone, Copy)]
+pub struct MessageHasher {
+    ctx: shake256_context,
+}
+
+impl MessageHasher {
+    /// Start hashing a message with the given nonce
+    pub fn new(nonce: &[u8]) -> Self {
+        let mut ctx = shake256_context::init();
+        ctx.inject(nonce);
+        Self { ctx }
+    }
+
+    /// Absorb the next chunk of the message
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.ctx.inject(chunk);
+    }
+
+    /// Absorb associated data, as `SecretKey::sign_with_aad` does before
+    /// the message
+    pub(crate) fn update_aad(&mut self, aad: &[u8]) {
+        self.ctx.inject_aad(aad);
+    }
+
+    /// Finalize the hash into a polynomial
+    pub fn finalize(mut self) -> Polynomial {
+        self.ctx.finalize();
+
+        // extract the data from rng one block at a time, and build the output
+        let mut buffer = [0u8; SHAKE256_RATE];
+        let mut ctr = buffer.len();
+        let mut res = [0u16; N];
+        let mut i = 0;
+        while i < N {
+            if ctr == buffer.len() {
+                self.ctx.extract_into(&mut buffer);
+                ctr = 0;
+            }
+            let coeff = (buffer[ctr] as u16) << 8 | (buffer[ctr + 1] as u16);
+            ctr += 2;
+            if coeff < 61445 {
+                res[i] = coeff % MODULUS;
+                i += 1;
+            }
+        }
+        Polynomial(res)
+    }
+
+    /// Finalize the hash into a polynomial, extracting `samples` 16-bit
+    /// samples up front and processing all of them with the same work,
+    /// whether they are accepted or not. Only when fewer than N of them
+    /// are accepted are more samples extracted, one at a time.
+    /// The result is the same as `finalize`.
+    pub fn finalize_with_limit(self, samples: usize) -> Polynomial {
+        self.finalize_fixed(samples).0
+    }
+
+    // as finalize_with_limit, also returning the number of coefficients
+    // set from the samples extracted up front
+    fn finalize_fixed(mut self, samples: usize) -> (Polynomial, usize) {
+        self.ctx.finalize();
+
+        let mut buffer = vec![0u8; 2 * samples];
+        self.ctx.extract_into(&mut buffer);
+
+        // every sample is written to position i, which only moves
+        // forward when the sample is accepted; a rejected sample is
+        // overwritten by the next one, and the samples past the N-th
+        // accepted one land in the extra slot
+        let mut res = [0u16; N + 1];
+        let mut i = 0;
+        for pair in buffer.chunks_exact(2) {
+            let coeff = (pair[0] as u16) << 8 | (pair[1] as u16);
+            res[i.min(N)] = coeff % MODULUS;
+            i += (coeff < 61445) as usize;
+        }
+        let fixed = i.min(N);
+
+        // the overflow case: continue the same SHAKE stream
+        let mut pair = [0u8; 2];
+        let mut i = fixed;
+        while i < N {
+            self.ctx.extract_into(&mut pair);
+            let coeff = (pair[0] as u16) << 8 | (pair[1] as u16);
+            if coeff < 61445 {
+                res[i] = coeff % MODULUS;
+                i += 1;
+            }
+        }
+
+        let mut output = [0u16; N];
+        output.copy_from_slice(&res[..N]);
+        (Polynomial(output), fixed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_hasher() {
+        let nonce = [42u8; 40];
+        let message = "testing message".as_bytes();
+
+        for chunk_size in [1, 3, 7, message.len()].iter() {
+            let mut hasher = MessageHasher::new(nonce.as_ref());
+            for chunk in message.chunks(*chunk_size) {
+                hasher.update(chunk);
+            }
+            assert_eq!(
+                hasher.finalize(),
+                Polynomial::from_hash_of_message(message, nonce.as_ref())
+            );
+        }
+
+        // no chunk at all hashes the empty message
+        assert_eq!(
+            MessageHasher::new(nonce.as_ref()).finalize(),
+            Polynomial::from_hash_of_message(&[], nonce.as_ref())
+        );
+    }
+
+    #[test]
+    fn test_finalize_with_limit() {
+        let nonce = [42u8; 40];
+        let message = "testing message".as_bytes();
+        let expected = Polynomial::from_hash_of_message(message, nonce.as_ref());
+
+        // too few samples up front exercise the overflow path
+        for &samples in [0, 1, N / 2, N, HASH_TO_POINT_SAMPLES, 2 * N].iter() {
+            let mut hasher = MessageHasher::new(nonce.as_ref());
+            hasher.update(message);
+            assert_eq!(hasher.finalize_with_limit(samples), expected);
+        }
+        assert_eq!(
+            Polynomial::from_hash_of_message_with_limit(
+                message,
+                nonce.as_ref(),
+                HASH_TO_POINT_SAMPLES
+            ),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_hash_to_point_samples_suffice() {
+        let message = "testing message".as_bytes();
+        for i in 0..10000u32 {
+            let mut nonce = [0u8; 40];
+            nonce[..4].copy_from_slice(&i.to_le_bytes());
+
+            let mut hasher = MessageHasher::new(nonce.as_ref());
+            hasher.update(message);
+            let (res, fixed) = hasher.finalize_fixed(HASH_TO_POINT_SAMPLES);
+            assert_eq!(fixed, N);
+            if i % 1000 == 0 {
+                assert_eq!(
+                    res,
+                    Polynomial::from_hash_of_message(message, nonce.as_ref())
+                );
+            }
+        }
+    }
+}
@@ -1,23 +1,142 @@
 mod dual_ntt_poly;
 mod dual_poly;
+mod message_hasher;
 mod ntt_poly;
 mod param;
 mod poly;
 
 use crate::{LOG_N, MODULUS, N, ONE_OVER_N};
+use std::ops::Mul;
 
 pub use dual_ntt_poly::DualNTTPolynomial;
 pub use dual_poly::DualPolynomial;
+pub use message_hasher::{MessageHasher, HASH_TO_POINT_SAMPLES};
 pub use ntt_poly::NTTPolynomial;
 pub use param::{INV_NTT_TABLE, NTT_TABLE};
 pub use poly::Polynomial;
 
+/// Hash a (nonce, message) pair into a point of Z_q\[x\]/(x^n + 1), as
+/// specified by `HashToPoint` in the Falcon specification: SHAKE256 is
+/// absorbed with the nonce then the message, and its output is parsed
+/// into 16-bit big-endian values with rejection of those >= 5q.
+///
+/// This is the same function as `Polynomial::from_hash_of_message`, with
+/// arguments in the spec order.
+pub fn hash_to_point(nonce: &[u8], message: &[u8]) -> Polynomial {
+    Polynomial::from_hash_of_message(message, nonce)
+}
+
+/// The inverse of a mod modulus, computed with the extended Euclidean
+/// algorithm; None if a is not invertible (or modulus is 0).
+pub const fn mod_inverse(a: u32, modulus: u32) -> Option<u32> {
+    if modulus == 0 {
+        return None;
+    }
+
+    // invariant: old_r = old_s * a mod modulus, r = s * a mod modulus
+    let (mut old_r, mut r) = ((a % modulus) as i64, modulus as i64);
+    let (mut old_s, mut s) = (1i64, 0i64);
+    while r != 0 {
+        let q = old_r / r;
+        let tmp = old_r - q * r;
+        old_r = r;
+        r = tmp;
+        let tmp = old_s - q * s;
+        old_s = s;
+        s = tmp;
+    }
+
+    if old_r != 1 {
+        return None;
+    }
+    Some(old_s.rem_euclid(modulus as i64) as u32)
+}
+
+/// The indices where the coefficients of a and b differ, together with
+/// the coefficients of a and b at those indices
+pub(crate) fn coeff_diff(a: &[u16], b: &[u16]) -> Vec<(usize, u16, u16)> {
+    a.iter()
+        .zip(b.iter())
+        .enumerate()
+        .filter(|(_, (x, y))| x != y)
+        .map(|(i, (&x, &y))| (i, x, y))
+        .collect()
+}
+
+// ONE_OVER_N is checked to be N^-1 mod q at compile time
+const _: () = assert!(matches!(
+    mod_inverse(N as u32, MODULUS as u32),
+    Some(x) if x == ONE_OVER_N
+));
+
+/// The [q, 2*q^2, 4 * q^3, ..., 2^LOG_N * q^(LOG_N+1)] constants over F,
+/// i.e., the i-th element is 2^i * q^(i+1).
+/// These are the bounds used by the NTT circuits to keep intermediate
+/// values positive without mod reductions.
+pub fn power_of_q_field<F: From<u64> + Mul<Output = F> + Copy>() -> Vec<F> {
+    let q = F::from(MODULUS as u64);
+    let two_q = F::from(2 * MODULUS as u64);
+
+    let mut res = vec![q];
+    for i in 1..LOG_N + 1 {
+        res.push(res[i - 1] * two_q);
+    }
+    res
+}
+
+/// The forward and inverse NTT tables of the active parameter set,
+/// i.e., the first N entries of NTT_TABLE and INV_NTT_TABLE.
+/// The i-th entries are psi^rev(i) and psi^-rev(i) mod q, where psi is
+/// a primitive 2N-th root of unity and rev reverses the LOG_N bits of i.
+pub fn export_ntt_tables() -> (Vec<u16>, Vec<u16>) {
+    (NTT_TABLE[..N].to_vec(), INV_NTT_TABLE[..N].to_vec())
+}
+
+/// Permute the coefficients of a polynomial by reversing the LOG_N bits
+/// of their indices, i.e., the i-th output coefficient is the rev(i)-th
+/// input coefficient. This is the ordering of NTT_TABLE, and of the NTT
+/// form: the i-th coefficient of `bit_reverse_permute` of the NTT form
+/// of p is p(psi^(2i+1)).
+pub fn bit_reverse_permute(poly: &Polynomial) -> Polynomial {
+    let mut res = Polynomial::default();
+    for (i, e) in res.0.iter_mut().enumerate() {
+        *e = poly.0[i.reverse_bits() >> (usize::BITS as usize - LOG_N)];
+    }
+    res
+}
+
+/// The inverse of `bit_reverse_permute`; as the bit reversal is an
+/// involution, this is the same permutation.
+pub fn inverse_bit_reverse_permute(poly: &Polynomial) -> Polynomial {
+    bit_reverse_permute(poly)
+}
+
+/// a * b mod q, for a, b < q; the twiddle multiplication of a butterfly
+#[inline(always)]
+fn mul_mod_q(a: u16, b: u16) -> u16 {
+    (a as u32 * b as u32 % MODULUS as u32) as u16
+}
+
 /// convert a polynomial into its NTT form
 pub(crate) fn ntt(input: &Polynomial) -> NTTPolynomial {
+    NTTPolynomial(ntt_partial(input, LOG_N))
+}
+
+/// Perform only the first `layers` butterfly stages of the NTT, in the
+/// same order as the full NTT, for experimenting with the intermediate
+/// states. `ntt_partial(x, 0)` is the identity, and `ntt_partial(x, LOG_N)`
+/// is the NTT of x.
+pub fn ntt_partial(input: &Polynomial, layers: usize) -> [u16; N] {
+    assert!(
+        layers <= LOG_N,
+        "the NTT has {} layers; {} requested",
+        LOG_N,
+        layers
+    );
     let mut output = input.0;
 
     let mut t = N;
-    for l in 0..LOG_N {
+    for l in 0..layers {
         let m = 1 << l;
         let ht = t / 2;
         let mut i = 0;
@@ -28,7 +147,7 @@ pub(crate) fn ntt(input: &Polynomial) -> NTTPolynomial {
             let mut j = j1;
             while j < j2 {
                 let u = output[j];
-                let v = (output[j + ht] as u32 * s as u32 % MODULUS as u32) as u16;
+                let v = mul_mod_q(output[j + ht], s);
                 output[j] = (u + v) % MODULUS;
                 output[j + ht] = (u + MODULUS - v) % MODULUS;
                 j += 1;
@@ -40,7 +159,7 @@ pub(crate) fn ntt(input: &Polynomial) -> NTTPolynomial {
         t = ht;
     }
 
-    NTTPolynomial(output)
+    output
 }
 
 /// convert an NTT form polynomial into its integer form
@@ -63,7 +182,7 @@ pub(crate) fn inv_ntt(input: &NTTPolynomial) -> Polynomial {
                 let v = output[j + t];
                 output[j] = (u + v) % MODULUS;
                 let w = (u + MODULUS - v) % MODULUS;
-                output[j + t] = (w as u32 * s as u32 % MODULUS as u32) as u16;
+                output[j + t] = mul_mod_q(w, s);
                 j += 1;
             }
 
@@ -78,3 +197,155 @@ pub(crate) fn inv_ntt(input: &NTTPolynomial) -> Polynomial {
     }
     Polynomial(output)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::SeedableRng;
+
+    // integers mod the Mersenne prime 2^61 - 1
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Fp61(u64);
+    const P61: u64 = (1 << 61) - 1;
+
+    impl From<u64> for Fp61 {
+        fn from(x: u64) -> Self {
+            Self(x % P61)
+        }
+    }
+
+    impl Mul for Fp61 {
+        type Output = Self;
+        fn mul(self, other: Self) -> Self {
+            Self(((self.0 as u128 * other.0 as u128) % P61 as u128) as u64)
+        }
+    }
+
+    fn pow_mod_q(base: u64, exp: u64) -> u64 {
+        let mut res = 1;
+        for _ in 0..exp {
+            res = res * base % MODULUS as u64;
+        }
+        res
+    }
+
+    #[test]
+    fn test_export_ntt_tables() {
+        // 7 is a primitive 2048-th root of unity mod q
+        assert_eq!(pow_mod_q(7, 1024), MODULUS as u64 - 1);
+        // so psi = 7^(1024/N) is a primitive 2N-th root of unity
+        let psi = pow_mod_q(7, (1024 / N) as u64);
+        assert_eq!(pow_mod_q(psi, N as u64), MODULUS as u64 - 1);
+
+        let (ntt_table, inv_ntt_table) = export_ntt_tables();
+        assert_eq!(ntt_table.len(), N);
+        assert_eq!(inv_ntt_table.len(), N);
+        for i in 0..N {
+            let rev = (i.reverse_bits() >> (usize::BITS as usize - LOG_N)) as u64;
+            assert_eq!(ntt_table[i] as u64, pow_mod_q(psi, rev));
+            // psi^-rev = psi^(2N - rev)
+            assert_eq!(inv_ntt_table[i] as u64, pow_mod_q(psi, 2 * N as u64 - rev));
+        }
+    }
+
+    #[test]
+    fn test_bit_reverse_permute() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        for _ in 0..10 {
+            let poly = Polynomial::rand(&mut rng);
+            let permuted = bit_reverse_permute(&poly);
+            assert_ne!(permuted, poly);
+            assert_eq!(bit_reverse_permute(&permuted), poly);
+            assert_eq!(inverse_bit_reverse_permute(&permuted), poly);
+        }
+
+        // NTT_TABLE in the natural order is [1, psi, psi^2, ...]
+        let mut table = Polynomial::default();
+        table.0.copy_from_slice(&NTT_TABLE[..N]);
+        let powers = bit_reverse_permute(&table);
+        let psi = powers.0[1] as u64;
+        assert_eq!(pow_mod_q(psi, N as u64), MODULUS as u64 - 1);
+        for (i, &e) in powers.0.iter().enumerate() {
+            assert_eq!(e as u64, pow_mod_q(psi, i as u64));
+        }
+
+        // and the NTT form in the natural order is [p(psi), p(psi^3), ...]
+        let poly = Polynomial::rand(&mut rng);
+        let evals = bit_reverse_permute(&Polynomial(ntt(&poly).0));
+        for i in [0, 1, 2, N / 2, N - 1] {
+            let x = pow_mod_q(psi, 2 * i as u64 + 1);
+            let eval = poly
+                .0
+                .iter()
+                .rev()
+                .fold(0, |acc, &c| (acc * x + c as u64) % MODULUS as u64);
+            assert_eq!(evals.0[i] as u64, eval);
+        }
+    }
+
+    #[test]
+    fn test_ntt_partial() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        for _ in 0..10 {
+            let poly = Polynomial::rand(&mut rng);
+            assert_eq!(ntt_partial(&poly, 0), poly.0);
+            assert_eq!(ntt_partial(&poly, LOG_N), ntt(&poly).0);
+
+            // the first layer: a butterfly between x[j] and x[j + N/2]
+            let one_layer = ntt_partial(&poly, 1);
+            let s = NTT_TABLE[1] as u32;
+            for j in 0..N / 2 {
+                let v = poly.0[j + N / 2] as u32 * s % MODULUS as u32;
+                let u = poly.0[j] as u32;
+                assert_eq!(one_layer[j] as u32, (u + v) % MODULUS as u32);
+                assert_eq!(
+                    one_layer[j + N / 2] as u32,
+                    (u + MODULUS as u32 - v) % MODULUS as u32
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_ntt_partial_too_many_layers() {
+        let poly = Polynomial::default();
+        ntt_partial(&poly, LOG_N + 1);
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(N as u32, MODULUS as u32), Some(ONE_OVER_N));
+        assert_eq!(mod_inverse(0, MODULUS as u32), None);
+        assert_eq!(mod_inverse(MODULUS as u32, MODULUS as u32), None);
+        assert_eq!(mod_inverse(1, MODULUS as u32), Some(1));
+        assert_eq!(mod_inverse(7, MODULUS as u32), Some(8778));
+        assert_eq!(mod_inverse(4, 8), None);
+        assert_eq!(mod_inverse(3, 0), None);
+
+        // the inverse NTT twiddles are the inverses of the forward ones
+        for (&s, &inv_s) in NTT_TABLE.iter().zip(INV_NTT_TABLE.iter()) {
+            assert_eq!(mod_inverse(s as u32, MODULUS as u32), Some(inv_s as u32));
+        }
+
+        for a in 1..MODULUS as u32 {
+            let inv = mod_inverse(a, MODULUS as u32).unwrap();
+            assert_eq!(a * inv % MODULUS as u32, 1);
+        }
+    }
+
+    #[test]
+    fn test_power_of_q_field() {
+        let powers = power_of_q_field::<Fp61>();
+        assert_eq!(powers.len(), LOG_N + 1);
+        for (i, &e) in powers.iter().enumerate() {
+            // 2^i * q^(i+1)
+            let mut expected = Fp61::from(1 << i);
+            for _ in 0..i + 1 {
+                expected = expected * Fp61::from(MODULUS as u64);
+            }
+            assert_eq!(e, expected);
+        }
+    }
+}
@@ -1,7 +1,7 @@
 // use crate::poly::{Polynomial, SMALL_SAMPLE_THRESHOLD};
 // use falcon_rust::{hash_message, inv_ntt, ntt, PublicKey};
 // use falcon_rust::{MODULUS, N};
-use super::ntt;
+use super::{coeff_diff, ntt};
 use crate::{Polynomial, MODULUS, N, U32_SAMPLE_THRESHOLD};
 use rand_chacha::ChaCha20Rng;
 use rand_core::{CryptoRng, RngCore, SeedableRng};
@@ -22,10 +22,14 @@ impl From<&Polynomial> for NTTPolynomial {
     }
 }
 
-impl Mul for NTTPolynomial {
-    type Output = Self;
-    fn mul(self, other: Self) -> <Self as Mul<Self>>::Output {
-        let mut res = self;
+/// Pointwise multiplication in the NTT domain.
+/// Both inputs must have their coefficients reduced mod q; the product of
+/// two reduced coefficients is below 2^28 and fits the u32 intermediate.
+impl<'a> Mul<&'a NTTPolynomial> for &'a NTTPolynomial {
+    type Output = NTTPolynomial;
+    fn mul(self, other: &'a NTTPolynomial) -> NTTPolynomial {
+        debug_assert!(self.is_reduced() && other.is_reduced());
+        let mut res = *self;
         res.0
             .iter_mut()
             .zip(other.0.iter())
@@ -35,10 +39,10 @@ impl Mul for NTTPolynomial {
     }
 }
 
-impl Add for NTTPolynomial {
-    type Output = Self;
-    fn add(self, other: Self) -> <Self as Add<Self>>::Output {
-        let mut res = self;
+impl<'a> Add<&'a NTTPolynomial> for &'a NTTPolynomial {
+    type Output = NTTPolynomial;
+    fn add(self, other: &'a NTTPolynomial) -> NTTPolynomial {
+        let mut res = *self;
         res.0
             .iter_mut()
             .zip(other.0.iter())
@@ -48,10 +52,10 @@ impl Add for NTTPolynomial {
     }
 }
 
-impl Sub for NTTPolynomial {
-    type Output = Self;
-    fn sub(self, other: Self) -> <Self as Add<Self>>::Output {
-        let mut res = self;
+impl<'a> Sub<&'a NTTPolynomial> for &'a NTTPolynomial {
+    type Output = NTTPolynomial;
+    fn sub(self, other: &'a NTTPolynomial) -> NTTPolynomial {
+        let mut res = *self;
         res.0
             .iter_mut()
             .zip(other.0.iter())
@@ -61,6 +65,37 @@ impl Sub for NTTPolynomial {
     }
 }
 
+// The by-value and mixed forms of the operators, all computed by the
+// by-reference ones above
+macro_rules! impl_ntt_poly_op {
+    ($trait:ident, $method:ident) => {
+        impl $trait for NTTPolynomial {
+            type Output = Self;
+            fn $method(self, other: Self) -> Self {
+                (&self).$method(&other)
+            }
+        }
+
+        impl<'a> $trait<&'a NTTPolynomial> for NTTPolynomial {
+            type Output = Self;
+            fn $method(self, other: &'a NTTPolynomial) -> Self {
+                (&self).$method(other)
+            }
+        }
+
+        impl<'a> $trait<NTTPolynomial> for &'a NTTPolynomial {
+            type Output = NTTPolynomial;
+            fn $method(self, other: NTTPolynomial) -> NTTPolynomial {
+                self.$method(&other)
+            }
+        }
+    };
+}
+
+impl_ntt_poly_op!(Mul, mul);
+impl_ntt_poly_op!(Add, add);
+impl_ntt_poly_op!(Sub, sub);
+
 impl NTTPolynomial {
     /// hash a message into a NTT form polynomial
     pub fn from_hash_of_message(message: &[u8], nonce: &[u8]) -> Self {
@@ -102,18 +137,40 @@ impl NTTPolynomial {
         res
     }
 
+    /// Lift signed coefficients to [0, MODULUS) and convert the
+    /// resulting polynomial into its NTT form
+    pub fn from_signed(coeffs: &[i16; N]) -> Self {
+        let mut poly = Polynomial::default();
+        for (e, &c) in poly.0.iter_mut().zip(coeffs.iter()) {
+            *e = (c as i32).rem_euclid(MODULUS as i32) as u16;
+        }
+        ntt(&poly)
+    }
+
     /// Access the coefficients
     pub fn coeff(&self) -> &[u16; N] {
         &self.0
     }
+
+    /// Return the indices where self and other differ, together with
+    /// the coefficients of self and other at those indices
+    pub fn diff(&self, other: &Self) -> Vec<(usize, u16, u16)> {
+        coeff_diff(&self.0, &other.0)
+    }
+
+    /// Check that all coefficients are reduced mod q
+    fn is_reduced(&self) -> bool {
+        self.0.iter().all(|&x| x < MODULUS)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::NTTPolynomial;
     use crate::arith::Polynomial;
+    use crate::{MODULUS, N};
     use rand_chacha::ChaCha20Rng;
-    use rand_core::SeedableRng;
+    use rand_core::{RngCore, SeedableRng};
 
     #[test]
     fn test_ntt_conversion() {
@@ -126,4 +183,101 @@ mod tests {
             assert_eq!(t, t_rec)
         }
     }
+
+    #[test]
+    fn test_ntt_mul() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let mut polys: Vec<NTTPolynomial> =
+            (0..100).map(|_| NTTPolynomial::rand(&mut rng)).collect();
+        polys.push(NTTPolynomial([MODULUS - 1; N]));
+
+        for a in polys.iter() {
+            for b in polys.iter().take(10) {
+                let c = *a * *b;
+                for i in 0..N {
+                    assert_eq!(
+                        c.0[i] as u64,
+                        a.0[i] as u64 * b.0[i] as u64 % MODULUS as u64
+                    )
+                }
+            }
+        }
+
+        // multiplication in the NTT domain agrees with the schoolbook
+        // multiplication in the coefficient domain
+        for _ in 0..10 {
+            let a = Polynomial::rand(&mut rng);
+            let b = Polynomial::rand(&mut rng);
+            let a_ntt: NTTPolynomial = (&a).into();
+            let b_ntt: NTTPolynomial = (&b).into();
+            let c: Polynomial = (&(a_ntt * b_ntt)).into();
+            assert_eq!(c, Polynomial::schoolbook_mul(&a, &b))
+        }
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_ntt_ops_by_reference() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        for _ in 0..10 {
+            let a = NTTPolynomial::rand(&mut rng);
+            let b = NTTPolynomial::rand(&mut rng);
+
+            let product = a * b;
+            assert_eq!(&a * &b, product);
+            assert_eq!(a * &b, product);
+            assert_eq!(&a * b, product);
+
+            let sum = a + b;
+            assert_eq!(&a + &b, sum);
+            assert_eq!(a + &b, sum);
+            assert_eq!(&a + b, sum);
+
+            let diff = a - b;
+            assert_eq!(&a - &b, diff);
+            assert_eq!(a - &b, diff);
+            assert_eq!(&a - b, diff);
+            assert_eq!(&diff + &b, a);
+        }
+    }
+
+    #[test]
+    fn test_ntt_diff() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let a = NTTPolynomial::rand(&mut rng);
+        assert!(a.diff(&a).is_empty());
+
+        let mut b = a;
+        b.0[0] = (a.0[0] + 1) % MODULUS;
+        b.0[N - 1] = (a.0[N - 1] + 2) % MODULUS;
+        assert_eq!(
+            a.diff(&b),
+            vec![(0, a.0[0], b.0[0]), (N - 1, a.0[N - 1], b.0[N - 1])]
+        );
+    }
+
+    #[test]
+    fn test_ntt_from_signed() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        for _ in 0..100 {
+            let mut signed = [0i16; N];
+            for e in signed.iter_mut() {
+                *e = (rng.next_u32() % (2 * MODULUS as u32 - 1)) as i16 - (MODULUS as i16 - 1);
+            }
+
+            // lift to a Polynomial, then convert
+            let mut poly = Polynomial::default();
+            for (e, &c) in poly.0.iter_mut().zip(signed.iter()) {
+                *e = if c < 0 {
+                    (c + MODULUS as i16) as u16
+                } else {
+                    c as u16
+                };
+            }
+            assert_eq!(
+                NTTPolynomial::from_signed(&signed),
+                NTTPolynomial::from(&poly)
+            );
+        }
+    }
 }
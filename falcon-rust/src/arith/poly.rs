@@ -1,8 +1,11 @@
-use super::{inv_ntt, NTTPolynomial};
-use crate::{shake256_context, MODULUS, MODULUS_MINUS_1_OVER_TWO, N, U32_SAMPLE_THRESHOLD};
+use super::{coeff_diff, inv_ntt, MessageHasher, NTTPolynomial};
+use crate::{FalconError, PublicKey, MODULUS, MODULUS_MINUS_1_OVER_TWO, N, U32_SAMPLE_THRESHOLD};
 use rand_chacha::ChaCha20Rng;
 use rand_core::{CryptoRng, RngCore, SeedableRng};
-use std::ops::{Add, Mul, Sub};
+use std::{
+    collections::HashMap,
+    ops::{Add, Mul, Sub},
+};
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Polynomial(pub(crate) [u16; N]);
@@ -100,10 +103,80 @@ impl Polynomial {
         res
     }
 
+    /// The conjugate p(x^-1) of p(x), i.e., the image of p under the
+    /// automorphism x -> x^-1 of Z_q\[x\]/(x^n + 1).
+    /// As x^-1 = -x^(n-1), the constant coefficient is kept, and the
+    /// coefficient of x^i, for i > 0, is negated and moved to x^(n-i).
+    pub fn conjugate(&self) -> Self {
+        let mut res = Self::default();
+        res.0[0] = self.0[0];
+        for i in 1..N {
+            res.0[N - i] = (MODULUS - self.0[i]) % MODULUS;
+        }
+        res
+    }
+
+    /// Multiply by the monomial x^k, i.e., rotate the coefficients by k
+    /// positions; as x^n = -1, the coefficients wrapping around are
+    /// negated. Any k is accepted, x^k depending only on k mod 2n.
+    pub fn rotate(&self, k: usize) -> Self {
+        let mut res = Self::default();
+        let k = k % (2 * N);
+        for (i, &e) in self.0.iter().enumerate() {
+            let j = (i + k) % (2 * N);
+            if j < N {
+                res.0[j] = e;
+            } else {
+                res.0[j - N] = (MODULUS - e) % MODULUS;
+            }
+        }
+        res
+    }
+
+    /// Same as `self * other`, but first checks that the coefficients
+    /// of both operands are in [0, MODULUS); the NTT based
+    /// multiplication silently gives a wrong result otherwise.
+    pub fn checked_mul(&self, other: &Self) -> Result<Self, FalconError> {
+        self.check_reduced()?;
+        other.check_reduced()?;
+        Ok(*self * *other)
+    }
+
+    /// Same as `self + other`, but returns None unless the coefficients
+    /// of both operands are in [0, MODULUS)
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        self.check_reduced().ok()?;
+        other.check_reduced().ok()?;
+        Some(*self + *other)
+    }
+
+    /// Same as `self - other`, but returns None unless the coefficients
+    /// of both operands are in [0, MODULUS); for larger coefficients
+    /// `x + MODULUS - y` may not be the representative of x - y.
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        self.check_reduced().ok()?;
+        other.check_reduced().ok()?;
+        Some(*self - *other)
+    }
+
+    /// Returns an error on the first coefficient not in [0, MODULUS)
+    fn check_reduced(&self) -> Result<(), FalconError> {
+        match self.0.iter().position(|&e| e >= MODULUS) {
+            Some(index) => Err(FalconError::UnreducedCoefficient {
+                index,
+                value: self.0[index],
+            }),
+            None => Ok(()),
+        }
+    }
+
     /// school book multiplication
     /// output = a(x) * b(x) mod x^N +1 mod MODULUS
     /// using school-book multiplications
     pub fn schoolbook_mul(a: &Self, b: &Self) -> Self {
+        // each product is reduced before accumulation, so every entry of
+        // the buffer is bounded by N * (MODULUS - 1) < 2^24 and the u32
+        // accumulator does not overflow for either parameter set
         let mut buf = [0u32; N << 1];
         let mut c = [0; N];
         for i in 0..N {
@@ -119,29 +192,128 @@ impl Polynomial {
         Self(c)
     }
 
-    /// hash a message into a polynomial
-    pub fn from_hash_of_message(message: &[u8], nonce: &[u8]) -> Self {
-        // initialize and finalize the rng
-        let mut rng = shake256_context::init();
-        rng.inject(nonce);
-        rng.inject(message);
-        rng.finalize();
-
-        // FIXME: give a better estimation of buffer size.
-        let buffer = rng.extract(N * 3);
-        let mut ctr = 0;
-        // extract the data from rng and build the output
-        let mut res = [0u16; N];
-        let mut i = 0;
-        while i < N {
-            let coeff = (buffer[ctr] as u16) << 8 | (buffer[ctr + 1] as u16);
-            ctr += 2;
-            if coeff < 61445 {
-                res[i] = coeff % MODULUS;
-                i += 1;
+    /// A non-constant time sampler for polynomials with a square l2 norm
+    /// strictly below bound.
+    ///
+    /// The coefficients are sampled uniformly from [-b, b], with b the
+    /// largest value for which the expected norm N * b(b+1)/3 is at most
+    /// bound, and the polynomial is rejected until its norm is below bound.
+    /// So the norm lands close to the bound whenever b >= 1.
+    /// Panics if bound is 0.
+    pub fn rand_with_l2_norm_below<R: RngCore>(rng: &mut R, bound: u64) -> Self {
+        assert!(bound > 0, "Invalid input: {}", bound);
+
+        let mut b = 0u64;
+        while b < MODULUS_MINUS_1_OVER_TWO as u64 - 1 && N as u64 * (b + 1) * (b + 2) / 3 <= bound {
+            b += 1;
+        }
+        let range = 2 * b as u32 + 1;
+        let threshold = u32::MAX - u32::MAX % range;
+
+        loop {
+            let mut res = Self::default();
+            for e in res.0.iter_mut() {
+                let mut tmp = rng.next_u32();
+                while tmp >= threshold {
+                    tmp = rng.next_u32();
+                }
+                // tmp in [0, 2b], shifted to [-b, b] mod q
+                *e = ((tmp % range) as u16 + MODULUS - b as u16) % MODULUS;
+            }
+            if res.l2_norm() < bound {
+                return res;
             }
         }
-        Self(res)
+    }
+
+    /// Sample a tuple (sig, v, hm) shaped like a Falcon verification
+    /// instance for the public key, without calling the signer:
+    /// - sig and v have random signs and magnitudes chosen so that
+    ///   l2_norm(sig) + l2_norm(v) is at most target_norm, and within
+    ///   2 * sqrt(target_norm / 2N) + 1 of it
+    /// - hm = v + sig * pk
+    ///
+    /// This is meant for exercising the verification circuits with
+    /// controlled inputs; the result is not a valid signature.
+    /// Panics if target_norm > 2N * 6143^2.
+    pub fn synth_verification_tuple<R: RngCore>(
+        pk: &PublicKey,
+        rng: &mut R,
+        target_norm: u64,
+    ) -> (Self, Self, Self) {
+        // keep the magnitudes below (q-1)/2 so the lifting to
+        // [-(q-1)/2, (q-1)/2] is unambiguous
+        let max_coeff = MODULUS_MINUS_1_OVER_TWO as u64 - 1;
+        assert!(
+            target_norm <= 2 * N as u64 * max_coeff * max_coeff,
+            "Invalid input: {}",
+            target_norm
+        );
+
+        // every coefficient gets magnitude c, and the first k (in a
+        // random order) get c + 1, where
+        //  2N * c^2 <= target_norm < 2N * (c + 1)^2
+        //  k = (target_norm - 2N * c^2) / (2c + 1) < 2N
+        let mut c = ((target_norm / (2 * N as u64)) as f64).sqrt() as u64;
+        while 2 * N as u64 * (c + 1) * (c + 1) <= target_norm {
+            c += 1;
+        }
+        while 2 * N as u64 * c * c > target_norm {
+            c -= 1;
+        }
+        let k = ((target_norm - 2 * N as u64 * c * c) / (2 * c + 1)) as usize;
+
+        let mut magnitudes = vec![c as u16; 2 * N];
+        for e in magnitudes.iter_mut().take(k) {
+            *e += 1;
+        }
+        // Fisher-Yates shuffle
+        for i in (1..2 * N).rev() {
+            let j = (rng.next_u32() as usize) % (i + 1);
+            magnitudes.swap(i, j);
+        }
+
+        let mut sig = Self::default();
+        let mut v = Self::default();
+        for (e, &m) in sig
+            .0
+            .iter_mut()
+            .chain(v.0.iter_mut())
+            .zip(magnitudes.iter())
+        {
+            *e = if rng.next_u32() & 1 == 0 || m == 0 {
+                m
+            } else {
+                MODULUS - m
+            };
+        }
+
+        let pk_poly: Self = pk.into();
+        let hm = v + sig * pk_poly;
+        (sig, v, hm)
+    }
+
+    /// hash a message into a polynomial
+    ///
+    /// The coefficients are rejection sampled from 16-bit SHAKE256
+    /// outputs, which are accepted with probability 61445/65536, so the
+    /// number of SHAKE extractions depends on the hash.
+    pub fn from_hash_of_message(message: &[u8], nonce: &[u8]) -> Self {
+        let mut hasher = MessageHasher::new(nonce);
+        hasher.update(message);
+        hasher.finalize()
+    }
+
+    /// hash a message into a polynomial, with the same result as
+    /// `from_hash_of_message`, extracting `samples` 16-bit samples up
+    /// front and processing all of them with the same work.
+    /// More samples are extracted only if fewer than N of them are
+    /// accepted; with `HASH_TO_POINT_SAMPLES` this is astronomically
+    /// unlikely.
+    pub fn from_hash_of_message_with_limit(message: &[u8], nonce: &[u8], samples: usize) -> Self {
+        let mut hasher = MessageHasher::new(nonce);
+        hasher.update(message);
+        hasher.finalize_with_limit(samples)
     }
 
     /// square of l2 norm of the polynomial
@@ -157,11 +329,45 @@ impl Polynomial {
         res
     }
 
+    /// Return the indices where self and other differ, together with
+    /// the coefficients of self and other at those indices
+    pub fn diff(&self, other: &Self) -> Vec<(usize, u16, u16)> {
+        coeff_diff(&self.0, &other.0)
+    }
+
     /// Access the coefficients
     pub fn coeff(&self) -> &[u16; N] {
         &self.0
     }
 
+    /// Number of non-zero coefficients
+    pub fn hamming_weight(&self) -> usize {
+        self.0.iter().filter(|&&e| e != 0).count()
+    }
+
+    /// The coefficients lifted to the centered range [-(q-1)/2, (q-1)/2]
+    pub fn to_centered(&self) -> [i16; N] {
+        let mut res = [0i16; N];
+        for (c, &e) in res.iter_mut().zip(self.0.iter()) {
+            *c = if e > MODULUS_MINUS_1_OVER_TWO {
+                e as i16 - MODULUS as i16
+            } else {
+                e as i16
+            };
+        }
+        res
+    }
+
+    /// Number of occurrences of each coefficient, with the coefficients
+    /// lifted to the centered range [-(q-1)/2, (q-1)/2]
+    pub fn coefficient_histogram(&self) -> HashMap<i16, usize> {
+        let mut res = HashMap::new();
+        for &centered in self.to_centered().iter() {
+            *res.entry(centered).or_insert(0) += 1;
+        }
+        res
+    }
+
     /// L infinity norm
     pub fn infinity_norm(&self) -> u64 {
         let mut res = 0u64;
@@ -183,6 +389,7 @@ impl Polynomial {
 #[cfg(test)]
 mod tests {
     use super::Polynomial;
+    use crate::{hash_to_point, FalconError, MODULUS, MODULUS_MINUS_1_OVER_TWO, N};
     use rand_chacha::ChaCha20Rng;
     use rand_core::SeedableRng;
 
@@ -197,4 +404,223 @@ mod tests {
             assert_eq!(tt, t)
         }
     }
+
+    #[test]
+    fn test_schoolbook_mul_worst_case() {
+        // all coefficients at their maximum value MODULUS - 1
+        let a = Polynomial([MODULUS - 1; N]);
+        let b = Polynomial([MODULUS - 1; N]);
+
+        assert_eq!(Polynomial::schoolbook_mul(&a, &b), a * b)
+    }
+
+    #[test]
+    fn test_hash_to_point_kat() {
+        // reference values computed with an independent SHAKE256
+        // implementation following the HashToPoint specification
+        let nonce: Vec<u8> = (0..40).collect();
+        let message = "Falcon hash-to-point test vector";
+        let point = hash_to_point(nonce.as_ref(), message.as_ref());
+
+        assert_eq!(
+            point.coeff()[..16],
+            [
+                8452, 6859, 2987, 55, 8193, 6995, 8158, 3795, 4520, 7949, 11184, 4447, 7657, 4560,
+                4056, 6566
+            ]
+        );
+        assert_eq!(point.coeff()[508..512], [2710, 5253, 2560, 7694]);
+        #[cfg(feature = "falcon-1024")]
+        assert_eq!(point.coeff()[1020..], [453, 5781, 5659, 1574]);
+
+        assert_eq!(
+            point,
+            Polynomial::from_hash_of_message(message.as_ref(), nonce.as_ref())
+        );
+    }
+
+    #[test]
+    fn test_synth_verification_tuple() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let pk = crate::KeyPair::keygen_with_seed("test seed".as_ref()).public_key;
+        let pk_poly: Polynomial = (&pk).into();
+
+        for &target_norm in [
+            0,
+            1,
+            2 * N as u64,
+            crate::SIG_L2_BOUND / 2,
+            crate::SIG_L2_BOUND - 1,
+            crate::SIG_L2_BOUND + 10000,
+        ]
+        .iter()
+        {
+            let (sig, v, hm) = Polynomial::synth_verification_tuple(&pk, &mut rng, target_norm);
+            let norm = sig.l2_norm() + v.l2_norm();
+            let c = ((target_norm / (2 * N as u64)) as f64).sqrt() as u64;
+            assert!(norm <= target_norm);
+            assert!(norm + 2 * c + 1 > target_norm);
+            assert_eq!(hm - sig * pk_poly, v);
+        }
+    }
+
+    #[test]
+    fn test_diff() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let a = Polynomial::rand(&mut rng);
+        assert!(a.diff(&a).is_empty());
+
+        let mut b = a;
+        b.0[7] = (a.0[7] + 1) % MODULUS;
+        assert_eq!(a.diff(&b), vec![(7, a.0[7], b.0[7])]);
+        assert_eq!(b.diff(&a), vec![(7, b.0[7], a.0[7])]);
+    }
+
+    #[test]
+    fn test_hamming_weight() {
+        assert_eq!(Polynomial::default().hamming_weight(), 0);
+
+        // a sparse polynomial: 1 + 2x^3 - x^5 - x^(N-1)
+        let mut poly = Polynomial::default();
+        poly.0[0] = 1;
+        poly.0[3] = 2;
+        poly.0[5] = MODULUS - 1;
+        poly.0[N - 1] = MODULUS - 1;
+        assert_eq!(poly.hamming_weight(), 4);
+
+        let histogram = poly.coefficient_histogram();
+        assert_eq!(histogram.len(), 4);
+        assert_eq!(histogram[&0], N - 4);
+        assert_eq!(histogram[&1], 1);
+        assert_eq!(histogram[&2], 1);
+        assert_eq!(histogram[&-1], 2);
+    }
+
+    #[test]
+    fn test_to_centered() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let mut poly = Polynomial::rand(&mut rng);
+        poly.0[0] = MODULUS_MINUS_1_OVER_TWO;
+        poly.0[1] = MODULUS_MINUS_1_OVER_TWO + 1;
+        poly.0[2] = 0;
+
+        let centered = poly.to_centered();
+        assert_eq!(centered[0], MODULUS_MINUS_1_OVER_TWO as i16);
+        assert_eq!(centered[1], -(MODULUS_MINUS_1_OVER_TWO as i16));
+        assert_eq!(centered[2], 0);
+        for (&c, &e) in centered.iter().zip(poly.coeff().iter()) {
+            assert!(c.abs() <= MODULUS_MINUS_1_OVER_TWO as i16);
+            assert_eq!((c as i32).rem_euclid(MODULUS as i32), e as i32);
+        }
+    }
+
+    #[test]
+    fn test_rotate() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let poly = Polynomial::rand(&mut rng);
+        assert_eq!(poly.rotate(0), poly);
+        assert_eq!(poly.rotate(2 * N), poly);
+        // x^N = -1
+        assert_eq!(poly.rotate(N) + poly, Polynomial::default());
+
+        for &k in [1, 5, N - 1, N, N + 3, 2 * N - 1, 2 * N, 3 * N + 7].iter() {
+            // x^k = x^(k mod N), negated if k mod 2N >= N
+            let mut monomial = Polynomial::default();
+            monomial.0[k % N] = if k % (2 * N) < N { 1 } else { MODULUS - 1 };
+            assert_eq!(
+                poly.rotate(k),
+                Polynomial::schoolbook_mul(&poly, &monomial),
+                "k = {}",
+                k
+            );
+        }
+    }
+
+    #[test]
+    fn test_conjugate() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        // x -> -x^(N-1)
+        let mut x = Polynomial::default();
+        x.0[1] = 1;
+        let mut x_conj = Polynomial::default();
+        x_conj.0[N - 1] = MODULUS - 1;
+        assert_eq!(x.conjugate(), x_conj);
+        assert_eq!(x * x_conj, Polynomial::one());
+
+        for _ in 0..10 {
+            let a = Polynomial::rand(&mut rng);
+            let b = Polynomial::rand(&mut rng);
+            assert_eq!(a.conjugate().conjugate(), a);
+            assert_eq!((a * b).conjugate(), a.conjugate() * b.conjugate());
+
+            // the constant coefficient of a * conjugate(b) is <a, b>
+            let inner_product = a
+                .coeff()
+                .iter()
+                .zip(b.coeff().iter())
+                .fold(0u64, |acc, (&ai, &bi)| {
+                    (acc + ai as u64 * bi as u64) % MODULUS as u64
+                });
+            assert_eq!((a * b.conjugate()).coeff()[0] as u64, inner_product);
+        }
+    }
+
+    #[test]
+    fn test_rand_with_l2_norm_below() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+
+        for &bound in [
+            1,
+            N as u64 / 3,
+            N as u64,
+            crate::SIG_L2_BOUND / 2,
+            crate::SIG_L2_BOUND,
+            u64::MAX,
+        ]
+        .iter()
+        {
+            for _ in 0..10 {
+                let poly = Polynomial::rand_with_l2_norm_below(&mut rng, bound);
+                assert!(poly.l2_norm() < bound);
+            }
+        }
+
+        // the norm is close to the bound
+        let poly = Polynomial::rand_with_l2_norm_below(&mut rng, crate::SIG_L2_BOUND);
+        assert!(poly.l2_norm() > crate::SIG_L2_BOUND / 10 * 9);
+    }
+
+    #[test]
+    fn test_checked_mul() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let a = Polynomial::rand(&mut rng);
+        let b = Polynomial::rand(&mut rng);
+        assert_eq!(a.checked_mul(&b), Ok(a * b));
+
+        let mut c = b;
+        c.0[3] = MODULUS + 5;
+        let err = Err(FalconError::UnreducedCoefficient {
+            index: 3,
+            value: MODULUS + 5,
+        });
+        assert_eq!(a.checked_mul(&c), err);
+        assert_eq!(c.checked_mul(&a), err);
+    }
+
+    #[test]
+    fn test_checked_add_sub() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let a = Polynomial::rand(&mut rng);
+        let b = Polynomial::rand(&mut rng);
+        assert_eq!(a.checked_add(&b), Some(a + b));
+        assert_eq!(a.checked_sub(&b), Some(a - b));
+        assert_eq!(a.checked_sub(&a), Some(Polynomial::default()));
+
+        let mut c = b;
+        c.0[N - 1] = MODULUS;
+        assert_eq!(a.checked_add(&c), None);
+        assert_eq!(c.checked_add(&a), None);
+        assert_eq!(a.checked_sub(&c), None);
+        assert_eq!(c.checked_sub(&a), None);
+    }
 }
@@ -0,0 +1,86 @@
+use crate::Polynomial;
+
+/// A polynomial whose coefficients are allocated in a constraint system.
+///
+/// This is implemented by the `PolyVar`s of both `falcon-r1cs` and
+/// `falcon-plonk`, so that circuit building code that is generic over
+/// this trait targets either backend.
+pub trait PolynomialVar: Sized {
+    /// The constraint system the variables are allocated in
+    type ConstraintSystem;
+    /// A variable of the constraint system
+    type Variable;
+    /// The NTT form of the polynomial, in the same constraint system
+    type NTTVar;
+    /// The error raised when building the circuit
+    type Error;
+
+    /// allocate private variables for a polynomial
+    fn alloc(cs: &mut Self::ConstraintSystem, poly: &Polynomial) -> Result<Self, Self::Error>;
+
+    /// Access the coefficients
+    fn coeff(&self) -> &[Self::Variable];
+
+    /// Generate the NTT form of the polynomial in-circuit,
+    /// with its coefficients reduced mod q
+    fn ntt(&self, cs: &mut Self::ConstraintSystem) -> Result<Self::NTTVar, Self::Error>;
+}
+
+/// Allocate a polynomial and generate its NTT form; the circuit building
+/// code of the tests, generic over the backend
+#[cfg(any(test, feature = "testing"))]
+pub fn alloc_and_ntt<P: PolynomialVar>(
+    cs: &mut P::ConstraintSystem,
+    poly: &Polynomial,
+) -> Result<(P, P::NTTVar), P::Error> {
+    let poly_var = P::alloc(cs, poly)?;
+    let ntt_var = poly_var.ntt(cs)?;
+    Ok((poly_var, ntt_var))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NTTPolynomial, N};
+    use rand_chacha::ChaCha20Rng;
+    use rand_core::SeedableRng;
+    use std::convert::Infallible;
+
+    // a "constraint system" where the variables are the values themselves,
+    // which counts the number of allocated variables
+    struct ClearPolyVar(Vec<u16>);
+
+    impl PolynomialVar for ClearPolyVar {
+        type ConstraintSystem = usize;
+        type Variable = u16;
+        type NTTVar = NTTPolynomial;
+        type Error = Infallible;
+
+        fn alloc(cs: &mut usize, poly: &Polynomial) -> Result<Self, Infallible> {
+            *cs += N;
+            Ok(Self(poly.coeff().to_vec()))
+        }
+
+        fn coeff(&self) -> &[u16] {
+            &self.0
+        }
+
+        fn ntt(&self, _cs: &mut usize) -> Result<NTTPolynomial, Infallible> {
+            let mut coeff = [0u16; N];
+            coeff.copy_from_slice(&self.0);
+            Ok(NTTPolynomial::from(&Polynomial(coeff)))
+        }
+    }
+
+    #[test]
+    fn test_polynomial_var() {
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let poly = Polynomial::rand(&mut rng);
+
+        let mut cs = 0;
+        let (poly_var, ntt_var) = alloc_and_ntt::<ClearPolyVar>(&mut cs, &poly).unwrap();
+        assert_eq!(cs, N);
+        assert_eq!(poly_var.coeff(), poly.coeff());
+        assert_eq!(ntt_var, NTTPolynomial::from(&poly));
+    }
+}
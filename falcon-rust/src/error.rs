@@ -0,0 +1,34 @@
+use std::fmt;
+
+/// Errors returned by the fallible APIs of this crate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FalconError {
+    /// A polynomial coefficient is not in [0, MODULUS)
+    UnreducedCoefficient { index: usize, value: u16 },
+    /// The signature is not a valid padded encoding
+    MalformedSignature,
+    /// The header byte does not match the parameter set
+    InvalidHeader { expected: u8, found: u8 },
+    /// The public key is not a canonical encoding
+    MalformedPublicKey,
+    /// The secret key is not a valid encoding
+    MalformedSecretKey,
+}
+
+impl fmt::Display for FalconError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FalconError::UnreducedCoefficient { index, value } => {
+                write!(f, "coefficient {} is not reduced: {}", index, value)
+            },
+            FalconError::MalformedSignature => write!(f, "malformed signature"),
+            FalconError::InvalidHeader { expected, found } => {
+                write!(f, "invalid header: expected {}, found {}", expected, found)
+            },
+            FalconError::MalformedPublicKey => write!(f, "malformed public key"),
+            FalconError::MalformedSecretKey => write!(f, "malformed secret key"),
+        }
+    }
+}
+
+impl std::error::Error for FalconError {}
@@ -5,11 +5,21 @@
 
 mod arith;
 mod binder;
+mod circuit;
+mod error;
 mod param;
 mod shake;
 mod structs;
+#[cfg(all(any(test, feature = "testing"), feature = "falcon-1024"))]
+mod test_vectors;
 
 pub use arith::*;
 use binder::*;
+#[cfg(any(test, feature = "testing"))]
+pub use circuit::alloc_and_ntt;
+pub use circuit::PolynomialVar;
+pub use error::FalconError;
 pub use param::*;
 pub use structs::*;
+#[cfg(all(any(test, feature = "testing"), feature = "falcon-1024"))]
+pub use test_vectors::test_vectors;
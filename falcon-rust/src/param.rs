@@ -15,6 +15,60 @@ pub use param1024::*;
 #[cfg(feature = "falcon-512")]
 pub use param512::*;
 
+/// The active parameter set, as a runtime value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Params {
+    pub n: usize,
+    pub log_n: usize,
+    pub modulus: u16,
+    pub pk_len: usize,
+    pub sk_len: usize,
+    pub sig_len: usize,
+    pub sig_l2_bound: u64,
+}
+
+/// Return the parameter set selected at compile time
+pub fn active_params() -> Params {
+    Params {
+        n: N,
+        log_n: LOG_N,
+        modulus: MODULUS,
+        pk_len: PK_LEN,
+        sk_len: SK_LEN,
+        sig_len: SIG_LEN,
+        sig_l2_bound: SIG_L2_BOUND,
+    }
+}
+
+/// The Falcon parameter sets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterSet {
+    Falcon512,
+    Falcon1024,
+}
+
+impl ParameterSet {
+    /// The parameter set of a signature with the given header byte,
+    /// i.e., `0x30 | logn` for the compressed signature format.
+    /// This does not depend on the parameter set compiled in, so it can
+    /// be used to dispatch a signature blob to the right verifier.
+    pub fn from_sig_header(header: u8) -> Option<Self> {
+        match header {
+            0x39 => Some(ParameterSet::Falcon512),
+            0x3a => Some(ParameterSet::Falcon1024),
+            _ => None,
+        }
+    }
+
+    /// log2 of the degree n
+    pub fn log_n(&self) -> usize {
+        match self {
+            ParameterSet::Falcon512 => 9,
+            ParameterSet::Falcon1024 => 10,
+        }
+    }
+}
+
 mod param512 {
     #![allow(dead_code)]
     pub const LOG_N: usize = 9;
@@ -52,3 +106,40 @@ mod param1024 {
     // pub const SIG_COEFF_BIT_LEN: usize = 12;
     pub const SIG_L2_BOUND: u64 = 70265242;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_active_params() {
+        let params = active_params();
+        assert_eq!(params.n, N);
+        assert_eq!(params.log_n, LOG_N);
+        assert_eq!(params.modulus, MODULUS);
+        assert_eq!(params.pk_len, PK_LEN);
+        assert_eq!(params.sk_len, SK_LEN);
+        assert_eq!(params.sig_len, SIG_LEN);
+        assert_eq!(params.sig_l2_bound, SIG_L2_BOUND);
+        assert_eq!(params.n, 1 << params.log_n);
+    }
+
+    #[test]
+    fn test_parameter_set() {
+        assert_eq!(
+            ParameterSet::from_sig_header(0x39),
+            Some(ParameterSet::Falcon512)
+        );
+        assert_eq!(
+            ParameterSet::from_sig_header(0x3a),
+            Some(ParameterSet::Falcon1024)
+        );
+        // the header of a public key, and of an uncompressed signature
+        assert_eq!(ParameterSet::from_sig_header(0x0a), None);
+        assert_eq!(ParameterSet::from_sig_header(0x5a), None);
+        assert_eq!(ParameterSet::from_sig_header(0), None);
+
+        let active = ParameterSet::from_sig_header(0x30 | LOG_N as u8).unwrap();
+        assert_eq!(active.log_n(), LOG_N);
+    }
+}
@@ -2,6 +2,9 @@ pub use crate::binder::shake256_context;
 use crate::binder::*;
 use libc::c_void;
 
+/// The domain separation tag injected before the associated data
+pub(crate) const AAD_DOMAIN_TAG: &[u8] = b"falcon.rs-aad";
+
 // wrappers for unsafe functions
 impl shake256_context {
     /// Initializing an RNG.
@@ -41,6 +44,19 @@ impl shake256_context {
         }
     }
 
+    /// Inject associated data, after the `AAD_DOMAIN_TAG` and its length as
+    /// a 64-bit big-endian integer, so that the (aad, message) split is
+    /// unambiguous. Nothing is injected for an empty aad, so that signing
+    /// with an empty aad is plain signing.
+    pub(crate) fn inject_aad(&mut self, aad: &[u8]) {
+        if aad.is_empty() {
+            return;
+        }
+        self.inject(AAD_DOMAIN_TAG);
+        self.inject((aad.len() as u64).to_be_bytes().as_ref());
+        self.inject(aad);
+    }
+
     /// Finalize the RNG
     pub fn finalize(&mut self) {
         unsafe { shake256_flip(self as *mut shake256_context) }
@@ -48,15 +64,21 @@ impl shake256_context {
 
     /// Extract data from the RNG
     pub fn extract(&mut self, len: usize) -> Vec<u8> {
-        let data = vec![0u8; len];
+        let mut data = vec![0u8; len];
+        self.extract_into(&mut data);
+        data
+    }
+
+    /// Extract data from the RNG into a caller provided buffer, without
+    /// allocating. Successive extractions continue the same output stream.
+    pub fn extract_into(&mut self, buf: &mut [u8]) {
         unsafe {
             shake256_extract(
                 self as *mut shake256_context,
-                data.as_ptr() as *mut c_void,
-                len as u64,
+                buf.as_mut_ptr() as *mut c_void,
+                buf.len() as u64,
             );
         }
-        data
     }
 }
 
@@ -68,4 +90,20 @@ mod test {
         let _rng1 = shake256_context::init();
         let _rng2 = shake256_context::init_with_seed("test seed".as_ref());
     }
+
+    #[test]
+    fn test_extract_into() {
+        let mut rng1 = shake256_context::init_with_seed("test seed".as_ref());
+        let mut rng2 = rng1;
+
+        let mut stream = Vec::new();
+        for _ in 0..100 {
+            stream.extend_from_slice(&rng1.extract(2));
+        }
+
+        let mut buf = [0u8; 200];
+        rng2.extract_into(&mut buf[..136]);
+        rng2.extract_into(&mut buf[136..]);
+        assert_eq!(stream[..], buf[..]);
+    }
 }
@@ -4,7 +4,7 @@ use rand_chacha::{
     rand_core::{RngCore, SeedableRng},
     ChaCha20Rng,
 };
-use zeroize::Zeroize;
+use zeroize::Zeroizing;
 
 use super::{PublicKey, SecretKey};
 
@@ -29,7 +29,8 @@ impl KeyPair {
         let mut shake256_context = shake256_context::init_with_seed(seed);
         let mut pk = [0u8; PK_LEN];
         let mut sk = [0u8; SK_LEN];
-        let mut buf = vec![0u8; KEYGEN_BUF_LEN];
+        // wiped when dropped, also if the assertion below fails
+        let mut buf = Zeroizing::new(vec![0u8; KEYGEN_BUF_LEN]);
 
         unsafe {
             assert!(
@@ -45,11 +46,50 @@ impl KeyPair {
                 ) == 0
             );
         }
-        buf.zeroize();
 
         Self {
             public_key: PublicKey(pk),
             secret_key: SecretKey(sk),
         }
     }
+
+    /// Check that the keypair is consistent:
+    /// - the public key is the one derived from the secret key,
+    ///   compared in constant time
+    /// - a signature on a canary message, produced with the secret key,
+    ///   is accepted by the public key
+    ///
+    /// Returns false, rather than panicking, on a malformed secret key.
+    pub fn validate(&self) -> bool {
+        match self.secret_key.try_make_public_key() {
+            Some(pk) if ct_eq(pk.as_bytes(), self.public_key.as_bytes()) => (),
+            _ => return false,
+        }
+
+        let mut seed = [0u8; 32];
+        let mut rng = ChaCha20Rng::from_entropy();
+        rng.fill_bytes(&mut seed);
+
+        let message = "falcon-rust keypair validation canary".as_bytes();
+        match self.secret_key.try_sign_with_seed(seed.as_ref(), message) {
+            Some(sig) => self.public_key.verify_rust(message, &sig),
+            None => false,
+        }
+    }
+
+    /// A keypair generated from a fixed, publicly known seed.
+    ///
+    /// The keypair is the same across calls, which makes it convenient
+    /// for tests, doctests and examples.
+    /// NOT for production use: anyone can recompute the secret key.
+    #[cfg(any(test, feature = "testing"))]
+    pub fn deterministic_for_testing() -> Self {
+        Self::keygen_with_seed("falcon-rust deterministic testing seed".as_ref())
+    }
+}
+
+// compare two byte strings of the same length without an early exit
+fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    assert_eq!(a.len(), b.len());
+    a.iter().zip(b.iter()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
 }
@@ -2,15 +2,28 @@ mod kp;
 mod pk;
 mod sig;
 mod sk;
+mod streaming;
+mod timings;
 
 pub use kp::KeyPair;
-pub use pk::PublicKey;
-pub use sig::Signature;
-pub use sk::SecretKey;
+#[cfg(feature = "ffi")]
+pub use pk::{cross_verify, CrossVerifyDiagnostics, CrossVerifyResult};
+#[cfg(feature = "parallel")]
+pub use pk::verify_batch_rust_parallel;
+pub use pk::{verify_batch_rust, verify_with_pk_ntt, AuditRecord, PreparedPublicKey, PublicKey};
+pub use sig::{encode_sig, signature_size_stats, ParsedSignature, Signature, SizeStats};
+pub use sk::{prehashed_message, SecretKey, PREHASH_DOMAIN};
+pub use streaming::{verify_stream, write_record, StreamingVerifier};
+pub use timings::VerifyTimings;
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        DualPolynomial, FalconError, NTTPolynomial, ParameterSet, Polynomial, LOG_N, N, PK_LEN,
+        SIG_L2_BOUND, SIG_LEN, SK_LEN,
+    };
+    use std::time::Duration;
 
     #[test]
     fn test_key_gen() {
@@ -20,6 +33,14 @@ mod tests {
         assert_eq!(pk2, keypair.public_key);
     }
 
+    #[test]
+    fn test_pk_from_sk() {
+        let keypair = KeyPair::keygen();
+        let pk: PublicKey = (&keypair.secret_key).into();
+
+        assert_eq!(pk, keypair.public_key);
+    }
+
     #[test]
     fn test_sign_and_verify() {
         let keypair = KeyPair::keygen();
@@ -29,9 +50,1080 @@ mod tests {
         let sig = keypair
             .secret_key
             .sign_with_seed("test seed".as_ref(), message.as_ref());
+        #[cfg(feature = "ffi")]
         assert!(keypair.public_key.verify(message.as_ref(), &sig));
         assert!(keypair.public_key.verify_rust(message.as_ref(), &sig));
         assert!(keypair.public_key.verify_parsed_sig(message.as_ref(), &sig));
-        assert!(!keypair.public_key.verify(message2.as_ref(), &sig))
+        #[cfg(feature = "ffi")]
+        assert!(!keypair.public_key.verify(message2.as_ref(), &sig));
+        assert!(!keypair.public_key.verify_rust(message2.as_ref(), &sig));
+    }
+
+    #[test]
+    fn test_parsed_sig() {
+        let keypair = KeyPair::keygen();
+        let keypair2 = KeyPair::keygen();
+
+        let message = "testing message";
+        let message2 = "another testing message";
+        let sig = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), message.as_ref());
+        let parsed = sig.parse_cached();
+
+        for pk in [keypair.public_key, keypair2.public_key].iter() {
+            for msg in [message, message2].iter() {
+                assert_eq!(
+                    parsed.verify_against(pk, msg.as_ref()),
+                    pk.verify_rust(msg.as_ref(), &sig)
+                );
+            }
+        }
+        assert!(parsed.verify_against(&keypair.public_key, message.as_ref()));
+        assert!(!parsed.verify_against(&keypair2.public_key, message.as_ref()));
+    }
+
+    #[test]
+    fn test_prepared_pk() {
+        let keypair = KeyPair::keygen();
+        let keypair2 = KeyPair::keygen();
+        let pk = keypair.public_key;
+
+        let pk_ntt = pk.to_ntt();
+        assert_eq!(pk_ntt, NTTPolynomial::from(&pk));
+        assert_eq!(pk_ntt, pk.to_ntt());
+
+        let prepared = pk.prepare();
+        assert_eq!(*prepared.ntt(), pk_ntt);
+        assert_eq!(*prepared.public_key(), pk);
+
+        let message = "testing message";
+        let message2 = "another testing message";
+        let sig = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), message.as_ref());
+        assert!(prepared.verify_rust(message.as_ref(), &sig));
+        assert!(!prepared.verify_rust(message2.as_ref(), &sig));
+        assert!(!keypair2
+            .public_key
+            .prepare()
+            .verify_rust(message.as_ref(), &sig));
+    }
+
+    #[test]
+    fn test_verify_with_pk_ntt() {
+        let keypair = KeyPair::keygen();
+        let keypair2 = KeyPair::keygen();
+
+        let message = "testing message";
+        let message2 = "another testing message";
+        let sig = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), message.as_ref());
+
+        let pk_ntt = keypair.public_key.to_ntt();
+        let pk_ntt2 = keypair2.public_key.to_ntt();
+        assert!(verify_with_pk_ntt(&pk_ntt, message.as_ref(), &sig));
+        assert!(!verify_with_pk_ntt(&pk_ntt, message2.as_ref(), &sig));
+        assert!(!verify_with_pk_ntt(&pk_ntt2, message.as_ref(), &sig));
+    }
+
+    #[test]
+    fn test_sign_with_empty_aad() {
+        let keypair = KeyPair::keygen();
+
+        let message = "testing message";
+        let sig = keypair.secret_key.sign_with_seed_and_aad(
+            "test seed".as_ref(),
+            "".as_ref(),
+            message.as_ref(),
+        );
+        let sig2 = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), message.as_ref());
+        // an empty aad is plain signing
+        assert_eq!(sig, sig2);
+        assert!(keypair.public_key.verify_rust(message.as_ref(), &sig));
+        assert!(keypair
+            .public_key
+            .verify_rust_with_aad("".as_ref(), message.as_ref(), &sig));
+    }
+
+    #[test]
+    fn test_sign_and_verify_rust_with_aad() {
+        let keypair = KeyPair::keygen();
+
+        let message = "testing message";
+        let aad = "associated data";
+        let sig = keypair.secret_key.sign_with_seed_and_aad(
+            "test seed".as_ref(),
+            aad.as_ref(),
+            message.as_ref(),
+        );
+        assert!(keypair
+            .public_key
+            .verify_rust_with_aad(aad.as_ref(), message.as_ref(), &sig));
+        assert!(!keypair.public_key.verify_rust_with_aad(
+            "other associated data".as_ref(),
+            message.as_ref(),
+            &sig
+        ));
+        assert!(!keypair
+            .public_key
+            .verify_rust_with_aad("".as_ref(), message.as_ref(), &sig));
+        assert!(!keypair.public_key.verify_rust(message.as_ref(), &sig));
+
+        // moving bytes between the aad and the message is detected
+        assert!(!keypair.public_key.verify_rust_with_aad(
+            "associated".as_ref(),
+            " datatesting message".as_ref(),
+            &sig
+        ));
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_sign_and_verify_with_aad() {
+        let keypair = KeyPair::keygen();
+
+        let message = "testing message";
+        let aad = "associated data";
+        let aad2 = "other associated data";
+        let sig = keypair.secret_key.sign_with_seed_and_aad(
+            "test seed".as_ref(),
+            aad.as_ref(),
+            message.as_ref(),
+        );
+        assert!(keypair
+            .public_key
+            .verify_with_aad(aad.as_ref(), message.as_ref(), &sig));
+        assert!(!keypair
+            .public_key
+            .verify_with_aad(aad2.as_ref(), message.as_ref(), &sig));
+        assert!(!keypair
+            .public_key
+            .verify_with_aad("".as_ref(), message.as_ref(), &sig));
+        assert!(!keypair.public_key.verify(message.as_ref(), &sig));
+
+        // moving bytes between the aad and the message is detected
+        assert!(!keypair.public_key.verify_with_aad(
+            "associated".as_ref(),
+            " datatesting message".as_ref(),
+            &sig
+        ));
+
+        let sig = keypair
+            .secret_key
+            .sign_with_aad(aad.as_ref(), message.as_ref());
+        assert!(keypair
+            .public_key
+            .verify_with_aad(aad.as_ref(), message.as_ref(), &sig));
+
+        // an empty aad is plain signing
+        let sig = keypair.secret_key.sign_with_seed_and_aad(
+            "test seed".as_ref(),
+            "".as_ref(),
+            message.as_ref(),
+        );
+        assert!(keypair
+            .public_key
+            .verify_with_aad("".as_ref(), message.as_ref(), &sig));
+        assert!(keypair.public_key.verify(message.as_ref(), &sig));
+
+        // a plain signature over be64(len(aad)) || aad || message does not
+        // verify as a signature with aad over message, nor the other way
+        let mut encoded = (aad.len() as u64).to_be_bytes().to_vec();
+        encoded.extend_from_slice(aad.as_ref());
+        encoded.extend_from_slice(message.as_ref());
+        let plain_sig = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), &encoded);
+        assert!(keypair.public_key.verify(&encoded, &plain_sig));
+        assert!(!keypair
+            .public_key
+            .verify_with_aad(aad.as_ref(), message.as_ref(), &plain_sig));
+        let aad_sig = keypair.secret_key.sign_with_seed_and_aad(
+            "test seed".as_ref(),
+            aad.as_ref(),
+            message.as_ref(),
+        );
+        assert!(!keypair.public_key.verify(&encoded, &aad_sig));
+    }
+
+    #[test]
+    fn test_deterministic_keypair() {
+        let keypair = KeyPair::deterministic_for_testing();
+        let keypair2 = KeyPair::deterministic_for_testing();
+        assert_eq!(keypair, keypair2);
+        assert_eq!(keypair.secret_key.make_public_key(), keypair.public_key);
+
+        let message = "testing message";
+        let sig = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), message.as_ref());
+        #[cfg(feature = "ffi")]
+        assert!(keypair.public_key.verify(message.as_ref(), &sig));
+        assert!(keypair.public_key.verify_rust(message.as_ref(), &sig));
+    }
+
+    #[test]
+    fn test_verify_batch_rust() {
+        let keypairs: Vec<KeyPair> = (0..3).map(|_| KeyPair::keygen()).collect();
+        let message = "testing message";
+        let message2 = "another testing message";
+        let sigs: Vec<Signature> = (0..12)
+            .map(|i| keypairs[i % 3].secret_key.sign(message.as_ref()))
+            .collect();
+
+        // every fourth tuple has the wrong message, every fifth the wrong key
+        let batch: Vec<(&PublicKey, &[u8], &Signature)> = (0..12)
+            .map(|i| {
+                let pk = &keypairs[if i % 5 == 4 { (i + 1) % 3 } else { i % 3 }].public_key;
+                let msg = if i % 4 == 3 { message2 } else { message };
+                (pk, msg.as_ref(), &sigs[i])
+            })
+            .collect();
+        let expected: Vec<bool> = (0..12).map(|i| i % 4 != 3 && i % 5 != 4).collect();
+
+        assert_eq!(verify_batch_rust(&batch), expected);
+        assert!(verify_batch_rust(&[]).is_empty());
+        #[cfg(feature = "parallel")]
+        {
+            assert_eq!(verify_batch_rust_parallel(&batch), expected);
+            assert_eq!(
+                verify_batch_rust_parallel(&batch),
+                verify_batch_rust(&batch)
+            );
+            assert!(verify_batch_rust_parallel(&[]).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_verify_stream() {
+        let keypair = KeyPair::keygen();
+        let pk = keypair.public_key;
+        let messages = ["testing message".as_bytes(), &[], &[7u8; 10000]];
+        let sigs: Vec<Signature> = messages
+            .iter()
+            .map(|m| keypair.secret_key.sign(m))
+            .collect();
+        let mut malformed = sigs[0].as_bytes().to_vec();
+        malformed[0] ^= 1;
+
+        let mut log = Vec::new();
+        for (m, sig) in messages.iter().zip(sigs.iter()) {
+            write_record(&mut log, m, sig).unwrap();
+        }
+        // a wrong message
+        write_record(&mut log, messages[0], &sigs[1]).unwrap();
+        // a malformed signature, with the record framing intact
+        log.extend_from_slice((malformed.len() as u64).to_be_bytes().as_ref());
+        log.extend_from_slice(&malformed);
+        log.extend_from_slice((messages[0].len() as u64).to_be_bytes().as_ref());
+        log.extend_from_slice(messages[0]);
+        write_record(&mut log, messages[0], &sigs[0]).unwrap();
+
+        let res: Vec<bool> = verify_stream(&pk, log.as_slice())
+            .map(|r| r.unwrap())
+            .collect();
+        assert_eq!(res, vec![true, true, true, false, false, true]);
+        assert_eq!(verify_stream(&pk, &[][..]).count(), 0);
+
+        // a malformed signature cannot be written
+        let mut bad_header = sigs[0];
+        bad_header.0[0] ^= 1;
+        let mut out = Vec::new();
+        let err = write_record(&mut out, messages[0], &bad_header).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(out.is_empty());
+
+        // a truncated record is an error, which ends the stream
+        let truncated = &log[..log.len() - 1];
+        let res: Vec<_> = verify_stream(&pk, truncated).collect();
+        assert_eq!(res.len(), 6);
+        assert!(res[..5].iter().all(|r| r.is_ok()));
+        assert_eq!(
+            res[5].as_ref().unwrap_err().kind(),
+            std::io::ErrorKind::UnexpectedEof
+        );
+
+        // so is a signature longer than SIG_LEN
+        let mut too_long = Vec::new();
+        too_long.extend_from_slice((SIG_LEN as u64 + 1).to_be_bytes().as_ref());
+        too_long.extend_from_slice(&[0u8; 8]);
+        let res: Vec<_> = verify_stream(&pk, too_long.as_slice()).collect();
+        assert_eq!(res.len(), 1);
+        assert_eq!(
+            res[0].as_ref().unwrap_err().kind(),
+            std::io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn test_sign_prehashed() {
+        let keypair = KeyPair::keygen();
+        let pk = keypair.public_key;
+        let digest = [1u8; 64];
+        let mut digest2 = digest;
+        digest2[63] ^= 1;
+
+        let sig = keypair
+            .secret_key
+            .sign_prehashed(&digest, "test seed".as_ref());
+        assert!(pk.verify_rust(&prehashed_message(&digest), &sig));
+        assert!(!pk.verify_rust(&prehashed_message(&digest2), &sig));
+        #[cfg(feature = "ffi")]
+        assert!(pk.verify(&prehashed_message(&digest), &sig));
+
+        // domain separation: not a signature on the bare digest
+        assert!(!pk.verify_rust(digest.as_ref(), &sig));
+        let plain_sig = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), digest.as_ref());
+        assert_ne!(sig, plain_sig);
+        assert!(!pk.verify_rust(&prehashed_message(&digest), &plain_sig));
+    }
+
+    #[test]
+    fn test_sign_with_witness() {
+        let keypair = KeyPair::keygen();
+        let message = "testing message";
+        let (sig, v) = keypair.secret_key.sign_with_witness(
+            "test seed".as_ref(),
+            message.as_ref(),
+            &keypair.public_key,
+        );
+        assert_eq!(
+            sig,
+            keypair
+                .secret_key
+                .sign_with_seed("test seed".as_ref(), message.as_ref())
+        );
+        assert_eq!(v, keypair.public_key.recover_v(message.as_ref(), &sig));
+
+        // (sig, v) is the short vector checked by the verification
+        let sig_u = Polynomial::from(&sig);
+        let hm = Polynomial::from_hash_of_message(message.as_ref(), sig.nonce());
+        assert_eq!(v + sig_u * Polynomial::from(&keypair.public_key), hm);
+        assert!(sig_u.l2_norm() + v.l2_norm() <= SIG_L2_BOUND);
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_verify_with_buffer() {
+        let keypair = KeyPair::keygen();
+        let message = "testing message";
+        let message2 = "another testing message";
+
+        // one buffer for all the verifications; a longer one is fine
+        let mut buf = vec![0u8; crate::VERIFY_BUF_LEN + 1];
+        for i in 0..10 {
+            let sig = keypair
+                .secret_key
+                .sign_with_seed(&[i as u8], message.as_ref());
+            let pk = keypair.public_key;
+            assert!(pk.verify_with_buffer(message.as_ref(), &sig, &mut buf));
+            assert!(!pk.verify_with_buffer(message2.as_ref(), &sig, &mut buf));
+            assert!(pk.verify_with_buffer(
+                message.as_ref(),
+                &sig,
+                &mut buf[..crate::VERIFY_BUF_LEN]
+            ));
+        }
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    #[should_panic(expected = "Invalid buffer length")]
+    fn test_verify_with_short_buffer() {
+        let keypair = KeyPair::keygen();
+        let message = "testing message";
+        let sig = keypair.secret_key.sign(message.as_ref());
+        let mut buf = vec![0u8; crate::VERIFY_BUF_LEN - 1];
+        keypair
+            .public_key
+            .verify_with_buffer(message.as_ref(), &sig, &mut buf);
+    }
+
+    #[test]
+    fn test_verify_rust_with_bound() {
+        let keypair = KeyPair::keygen();
+        let message = "testing message";
+        let message2 = "another testing message";
+        for i in 0..10 {
+            let sig = keypair
+                .secret_key
+                .sign_with_seed(&[i as u8], message.as_ref());
+            let pk = keypair.public_key;
+            assert!(pk.verify_rust_with_bound(message.as_ref(), &sig, SIG_L2_BOUND));
+            assert!(!pk.verify_rust_with_bound(message2.as_ref(), &sig, SIG_L2_BOUND));
+
+            assert!(!pk.verify_rust_with_bound(message.as_ref(), &sig, 0));
+            // any well formed signature passes with no bound, even
+            // on the wrong message
+            assert!(pk.verify_rust_with_bound(message.as_ref(), &sig, u64::MAX));
+            assert!(pk.verify_rust_with_bound(message2.as_ref(), &sig, u64::MAX));
+        }
+    }
+
+    #[test]
+    fn test_verify_with_margin() {
+        let keypair = KeyPair::keygen();
+        let message = "testing message";
+        for i in 0..10 {
+            let sig = keypair
+                .secret_key
+                .sign_with_seed(&[i as u8], message.as_ref());
+            let margin = keypair
+                .public_key
+                .verify_with_margin(message.as_ref(), &sig)
+                .unwrap();
+            assert!(margin > 0);
+
+            let sig_u = Polynomial::from(&sig);
+            let v = keypair.public_key.recover_v(message.as_ref(), &sig);
+            assert_eq!(margin, SIG_L2_BOUND - sig_u.l2_norm() - v.l2_norm());
+        }
+
+        // a well formed signature whose u part alone exceeds the bound
+        let num_large = (SIG_L2_BOUND / (2047 * 2047)) as usize + 1;
+        let mut coeffs = [0i16; N];
+        coeffs[..num_large].iter_mut().for_each(|x| *x = 2047);
+        let mut bytes = vec![0x30 + LOG_N as u8];
+        bytes.extend_from_slice(&encode_sig(&[0u8; 40], &coeffs));
+        let sig = Signature::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            keypair
+                .public_key
+                .verify_with_margin(message.as_ref(), &sig),
+            None
+        );
+        assert!(!keypair.public_key.verify_rust(message.as_ref(), &sig));
+    }
+
+    #[test]
+    fn test_keypair_validate() {
+        let keypair = KeyPair::keygen();
+        assert!(keypair.validate());
+
+        // public key that does not match the secret key
+        let mut corrupted = keypair;
+        corrupted.public_key.0[10] ^= 1;
+        assert!(!corrupted.validate());
+        let mut corrupted = keypair;
+        corrupted.public_key.0[PK_LEN - 1] ^= 1;
+        assert!(!corrupted.validate());
+
+        // secret key with a wrong header byte
+        let mut corrupted = keypair;
+        corrupted.secret_key.0[0] ^= 0xFF;
+        assert!(!corrupted.validate());
+
+        // secret key with a modified coefficient of f
+        let mut corrupted = keypair;
+        corrupted.secret_key.0[10] ^= 1;
+        assert!(!corrupted.validate());
+
+        // keypair with mismatched keys
+        let keypair2 = KeyPair::keygen();
+        let mixed = KeyPair {
+            public_key: keypair2.public_key,
+            secret_key: keypair.secret_key,
+        };
+        assert!(!mixed.validate());
+    }
+
+    #[test]
+    fn test_sig_header() {
+        let keypair = KeyPair::keygen_with_seed("test seed".as_ref());
+        let message = "testing message";
+        let sig = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), message.as_ref());
+        let coeffs = sig.unpack();
+        assert_eq!(sig.try_unpack(), Ok(coeffs));
+
+        let header = 0x30 + LOG_N as u8;
+        for &bad_header in [
+            // the other parameter set
+            0x30 + (LOG_N as u8 ^ 3),
+            0x30 + LOG_N as u8 + 1,
+            // the constant-time format, also the secret key header
+            0x50 + LOG_N as u8,
+            // an unknown format
+            0x20 + LOG_N as u8,
+            // the public key header
+            LOG_N as u8,
+            0x00,
+            0xFF,
+        ]
+        .iter()
+        {
+            let err = FalconError::InvalidHeader {
+                expected: header,
+                found: bad_header,
+            };
+
+            let mut bad_sig = sig;
+            bad_sig.0[0] = bad_header;
+            assert_eq!(bad_sig.try_unpack(), Err(err));
+            assert!(!bad_sig.is_valid_encoding());
+            assert!(!bad_sig.is_canonical());
+
+            let mut bytes = sig.as_bytes().to_vec();
+            bytes[0] = bad_header;
+            assert_eq!(Signature::from_bytes(&bytes), Err(err));
+        }
+
+        // a good header with a malformed body
+        let mut bad_sig = sig;
+        bad_sig.0[41..].iter_mut().for_each(|x| *x = 0);
+        assert_eq!(bad_sig.try_unpack(), Err(FalconError::MalformedSignature));
+    }
+
+    #[test]
+    fn test_sig_encoding() {
+        // a fixed key: with a random one, the compressed body
+        // occasionally fills the whole buffer and leaves no padding
+        let keypair = KeyPair::keygen_with_seed("test seed".as_ref());
+        let message = "testing message";
+        let sig = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), message.as_ref());
+        assert!(sig.is_valid_encoding());
+        #[cfg(feature = "ffi")]
+        assert!(keypair.public_key.verify(message.as_ref(), &sig));
+
+        // wrong header
+        let mut bad_header = sig;
+        bad_header.0[0] ^= 1;
+        // non-zero byte in the padding
+        assert_eq!(sig.0[SIG_LEN - 1], 0);
+        let mut bad_padding = sig;
+        bad_padding.0[SIG_LEN - 1] = 1;
+        // the coefficients never terminate
+        let mut all_zeros = sig;
+        all_zeros.0[41..].iter_mut().for_each(|x| *x = 0);
+        let mut all_ones = sig;
+        all_ones.0[41..].iter_mut().for_each(|x| *x = 0xFF);
+
+        for bad_sig in [bad_header, bad_padding, all_zeros, all_ones].iter() {
+            assert!(!bad_sig.is_valid_encoding());
+            #[cfg(feature = "ffi")]
+            assert!(!keypair.public_key.verify(message.as_ref(), bad_sig));
+        }
+    }
+
+    #[test]
+    fn test_hex_and_base64() {
+        let keypair = KeyPair::keygen_with_seed("test seed".as_ref());
+        let pk = keypair.public_key;
+        let sig = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), "testing message".as_ref());
+
+        assert_eq!(PublicKey::from_hex(&pk.to_hex()), Ok(pk));
+        assert_eq!(PublicKey::from_base64(&pk.to_base64()), Ok(pk));
+        assert_eq!(Signature::from_hex(&sig.to_hex()), Ok(sig));
+        assert_eq!(Signature::from_base64(&sig.to_base64()), Ok(sig));
+        assert_eq!(pk.to_hex().len(), 2 * PK_LEN);
+
+        // the signature without its zero padding
+        let unpadded = &sig.as_bytes()[..sig.encoded_len()];
+        assert_eq!(Signature::from_hex(&hex::encode(unpadded)), Ok(sig));
+
+        // bad encodings
+        for s in ["", "0", "zz", "not base64!"].iter() {
+            assert_eq!(PublicKey::from_hex(s), Err(FalconError::MalformedPublicKey));
+            assert_eq!(Signature::from_hex(s), Err(FalconError::MalformedSignature));
+            assert_eq!(
+                PublicKey::from_base64(s),
+                Err(FalconError::MalformedPublicKey)
+            );
+            assert_eq!(
+                Signature::from_base64(s),
+                Err(FalconError::MalformedSignature)
+            );
+        }
+        // bad lengths
+        let pk_hex = pk.to_hex();
+        assert_eq!(
+            PublicKey::from_hex(&pk_hex[..pk_hex.len() - 2]),
+            Err(FalconError::MalformedPublicKey)
+        );
+        let mut long_sig = sig.as_bytes().to_vec();
+        long_sig.push(0);
+        assert_eq!(
+            Signature::from_base64(&base64::encode(&long_sig)),
+            Err(FalconError::MalformedSignature)
+        );
+    }
+
+    #[test]
+    fn test_over_norm_for_testing() {
+        let message = "testing message";
+        for _ in 0..10 {
+            let keypair = KeyPair::keygen();
+            let sig = keypair.secret_key.sign(message.as_ref());
+            let bad_sig = sig.over_norm_for_testing(&keypair.public_key, message.as_ref());
+
+            assert!(bad_sig.is_valid_encoding());
+            assert_eq!(bad_sig.nonce(), sig.nonce());
+            assert_eq!(
+                sig.coefficients()
+                    .iter()
+                    .zip(bad_sig.coefficients().iter())
+                    .filter(|(a, b)| a != b)
+                    .count(),
+                1
+            );
+            assert!(!keypair.public_key.verify_rust(message.as_ref(), &bad_sig));
+            #[cfg(feature = "ffi")]
+            assert!(!keypair.public_key.verify(message.as_ref(), &bad_sig));
+        }
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn test_cross_verify() {
+        let message = "testing message";
+        let message2 = "another testing message";
+        for i in 0..10 {
+            let keypair = KeyPair::keygen();
+            let sig = keypair.secret_key.sign(message.as_ref());
+            let pk = keypair.public_key;
+            for m in [message, message2].iter() {
+                let res = cross_verify(&pk, m.as_ref(), &sig);
+                assert!(res.agree(), "{}: {:?}", i, res);
+                assert_eq!(res.c_accepts, *m == message);
+                assert_eq!(res.diagnostics, None);
+            }
+        }
+
+        // a non-zero byte in the padding is rejected by the C verification
+        // only, as the Rust one does not look past the coefficients
+        let keypair = KeyPair::keygen_with_seed("test seed".as_ref());
+        let mut sig = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), message.as_ref());
+        sig.0[SIG_LEN - 1] = 1;
+        let res = cross_verify(&keypair.public_key, message.as_ref(), &sig);
+        assert!(!res.agree());
+        assert!(!res.c_accepts);
+        assert!(res.rust_accepts);
+        let diagnostics = res.diagnostics.unwrap();
+        assert!(!diagnostics.valid_encoding);
+        assert!(diagnostics.l2_norm.unwrap() <= SIG_L2_BOUND);
+
+        // a body that does not decode is rejected by both
+        sig.0[41..].iter_mut().for_each(|x| *x = 0xFF);
+        let res = cross_verify(&keypair.public_key, message.as_ref(), &sig);
+        assert!(res.agree());
+        assert!(!res.rust_accepts);
+    }
+
+    #[test]
+    fn test_verify_rust_strict() {
+        let keypair = KeyPair::keygen();
+        let message = "testing message";
+        let sig = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), message.as_ref());
+        assert!(keypair
+            .public_key
+            .verify_rust_strict(message.as_ref(), &sig));
+
+        // a well-formed signature whose u part is all zero:
+        // each 0 coefficient is encoded as 8 zero bits followed by a 1
+        let mut zero_sig = sig;
+        zero_sig.0[41..].iter_mut().for_each(|x| *x = 0);
+        for i in 0..N {
+            let bit = 9 * i + 8;
+            zero_sig.0[41 + bit / 8] |= 0x80 >> (bit % 8);
+        }
+        assert!(zero_sig.is_valid_encoding());
+        assert_eq!(Polynomial::from(&zero_sig), Polynomial::default());
+        assert!(!keypair
+            .public_key
+            .verify_rust_strict(message.as_ref(), &zero_sig));
+    }
+
+    #[test]
+    fn test_verify_rust_audit() {
+        let keypair = KeyPair::keygen();
+        let keypair2 = KeyPair::keygen();
+
+        let message = "testing message";
+        let message2 = "another testing message";
+        let sig = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), message.as_ref());
+
+        for pk in [keypair.public_key, keypair2.public_key].iter() {
+            for msg in [message, message2].iter() {
+                let record = pk.verify_rust_audit(msg.as_ref(), &sig).unwrap();
+                assert_eq!(record.accepted, pk.verify_rust(msg.as_ref(), &sig));
+                assert_eq!(record.accepted, record.l2_norm <= SIG_L2_BOUND);
+            }
+        }
+
+        // the fingerprint only depends on the message and the nonce
+        let record = keypair
+            .public_key
+            .verify_rust_audit(message.as_ref(), &sig)
+            .unwrap();
+        let record2 = keypair2
+            .public_key
+            .verify_rust_audit(message.as_ref(), &sig)
+            .unwrap();
+        let record3 = keypair
+            .public_key
+            .verify_rust_audit(message2.as_ref(), &sig)
+            .unwrap();
+        assert!(record.accepted);
+        assert_eq!(record.hm_fingerprint, record2.hm_fingerprint);
+        assert_ne!(record.hm_fingerprint, record3.hm_fingerprint);
+
+        let mut bad_sig = sig;
+        bad_sig.0[0] ^= 1;
+        assert_eq!(
+            keypair
+                .public_key
+                .verify_rust_audit(message.as_ref(), &bad_sig),
+            Err(FalconError::MalformedSignature)
+        );
+    }
+
+    #[test]
+    fn test_streaming_verifier() {
+        let keypair = KeyPair::keygen();
+        let keypair2 = KeyPair::keygen();
+
+        let message = "a much longer testing message, fed to the verifier in chunks";
+        let message2 = "another testing message";
+        let sig = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), message.as_ref());
+
+        for pk in [keypair.public_key, keypair2.public_key].iter() {
+            for msg in [message, message2].iter() {
+                for chunk_size in [1, 5, msg.len()].iter() {
+                    let mut verifier = StreamingVerifier::new(pk, &sig).unwrap();
+                    for chunk in msg.as_bytes().chunks(*chunk_size) {
+                        verifier.update(chunk);
+                    }
+                    assert_eq!(verifier.finalize(), pk.verify_rust(msg.as_ref(), &sig));
+                }
+            }
+        }
+
+        let mut verifier = StreamingVerifier::new(&keypair.public_key, &sig).unwrap();
+        verifier.update(message[..10].as_ref());
+        verifier.update(message[10..].as_ref());
+        assert!(verifier.finalize());
+
+        // a malformed signature is an error, not a panic
+        let mut bad_header = sig;
+        bad_header.0[0] ^= 1;
+        assert!(matches!(
+            StreamingVerifier::new(&keypair.public_key, &bad_header),
+            Err(FalconError::MalformedSignature)
+        ));
+    }
+
+    #[test]
+    fn test_verify_timed() {
+        let keypair = KeyPair::keygen();
+        let keypair2 = KeyPair::keygen();
+
+        let message = "testing message";
+        let message2 = "another testing message";
+        let sig = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), message.as_ref());
+
+        for pk in [keypair.public_key, keypair2.public_key].iter() {
+            for msg in [message, message2].iter() {
+                let (res, timings) = pk.verify_timed(msg.as_ref(), &sig);
+                assert_eq!(res, pk.verify_rust(msg.as_ref(), &sig));
+
+                // the phases cover the whole verification
+                assert!(timings.sum() <= timings.total);
+                assert!(
+                    timings.total - timings.sum() <= timings.total / 10 + Duration::from_millis(1)
+                );
+                if cfg!(not(feature = "profiling")) {
+                    assert_eq!(timings, VerifyTimings::default());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_pk_from_bytes() {
+        let keypair = KeyPair::keygen();
+        let pk = keypair.public_key;
+        assert_eq!(PublicKey::from_bytes(pk.as_bytes()), Ok(pk));
+
+        // a key for the other parameter set
+        let (other_log_n, other_pk_len) = if N == 1024 { (9, 897) } else { (10, 1793) };
+        let mut other_pk = vec![0u8; other_pk_len];
+        other_pk[0] = other_log_n;
+        assert_eq!(
+            PublicKey::from_bytes(other_pk.as_ref()),
+            Err(FalconError::InvalidHeader {
+                expected: LOG_N as u8,
+                found: other_log_n,
+            })
+        );
+
+        // an unreduced coefficient
+        let mut bytes = pk.as_bytes().to_vec();
+        bytes[1] = 0xff;
+        bytes[2] |= 0xfc;
+        assert_eq!(
+            PublicKey::from_bytes(bytes.as_ref()),
+            Err(FalconError::MalformedPublicKey)
+        );
+
+        // trailing padding, and truncation
+        let mut bytes = pk.as_bytes().to_vec();
+        bytes.push(0);
+        assert_eq!(
+            PublicKey::from_bytes(bytes.as_ref()),
+            Err(FalconError::MalformedPublicKey)
+        );
+        assert_eq!(
+            PublicKey::from_bytes(pk.as_bytes()[..PK_LEN - 1].as_ref()),
+            Err(FalconError::MalformedPublicKey)
+        );
+        assert_eq!(
+            PublicKey::from_bytes(&[]),
+            Err(FalconError::MalformedPublicKey)
+        );
+    }
+
+    #[test]
+    fn test_sk_debug_redacted() {
+        let keypair = KeyPair::keygen();
+        let sk = keypair.secret_key;
+        assert_eq!(format!("{:?}", sk), format!("SecretKey([REDACTED; {}])", SK_LEN));
+
+        // no run of the key bytes shows up, even within a KeyPair
+        let key_bytes = format!("{:?}", &sk.as_bytes()[1..9]);
+        let key_bytes = key_bytes.trim_start_matches('[').trim_end_matches(']');
+        for output in [format!("{:?}", sk), format!("{:#?}", keypair)].iter() {
+            assert!(output.contains("REDACTED"));
+            assert!(!output.contains(key_bytes));
+        }
+    }
+
+    #[test]
+    fn test_malformed_sk_failure_paths() {
+        // a failing C routine makes the try_ variants return None and
+        // the others panic; either way the `Zeroizing` scratch buffer is
+        // dropped on the way out. This only checks the failure paths:
+        // the wipe itself cannot be observed after the drop.
+        let keypair = KeyPair::keygen();
+        let mut bad_sk = keypair.secret_key;
+        bad_sk.0[0] ^= 1;
+
+        let message = "testing message".as_bytes();
+        assert!(bad_sk
+            .try_sign_with_seed("test seed".as_ref(), message)
+            .is_none());
+        assert!(bad_sk.try_make_public_key().is_none());
+
+        let res = std::panic::catch_unwind(|| {
+            bad_sk.sign_with_seed_and_aad("test seed".as_ref(), "aad".as_ref(), message)
+        });
+        assert!(res.is_err());
+        let res = std::panic::catch_unwind(|| bad_sk.sign_with_seed("test seed".as_ref(), message));
+        assert!(res.is_err());
+        let res = std::panic::catch_unwind(|| bad_sk.make_public_key());
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_sk_from_bytes() {
+        let keypair = KeyPair::keygen();
+        let sk = keypair.secret_key;
+        assert_eq!(SecretKey::from_bytes(sk.as_bytes()), Ok(sk));
+
+        let mut bytes = sk.as_bytes().to_vec();
+        bytes[0] ^= 1;
+        assert_eq!(
+            SecretKey::from_bytes(bytes.as_ref()),
+            Err(FalconError::InvalidHeader {
+                expected: 0x50 + LOG_N as u8,
+                found: bytes[0],
+            })
+        );
+
+        // f = 0 is not invertible
+        let mut bytes = sk.as_bytes().to_vec();
+        for e in bytes[1..].iter_mut() {
+            *e = 0;
+        }
+        assert_eq!(
+            SecretKey::from_bytes(bytes.as_ref()),
+            Err(FalconError::MalformedSecretKey)
+        );
+
+        let mut bytes = sk.as_bytes().to_vec();
+        bytes.push(0);
+        assert_eq!(
+            SecretKey::from_bytes(bytes.as_ref()),
+            Err(FalconError::MalformedSecretKey)
+        );
+        assert_eq!(
+            SecretKey::from_bytes(&[]),
+            Err(FalconError::MalformedSecretKey)
+        );
+    }
+
+    #[test]
+    fn test_sig_parameter_set() {
+        let keypair = KeyPair::keygen();
+        let mut sig = keypair.secret_key.sign("testing message".as_ref());
+        let expected = if N == 512 {
+            ParameterSet::Falcon512
+        } else {
+            ParameterSet::Falcon1024
+        };
+        assert_eq!(sig.parameter_set(), Some(expected));
+
+        sig.0[0] = 0x30 | (19 - LOG_N as u8);
+        assert_ne!(sig.parameter_set(), Some(expected));
+        assert!(sig.parameter_set().is_some());
+        sig.0[0] = LOG_N as u8;
+        assert_eq!(sig.parameter_set(), None);
+    }
+
+    #[test]
+    fn test_sig_from_bytes() {
+        let keypair = KeyPair::keygen_with_seed("test seed".as_ref());
+        let message = "testing message";
+        let sig = keypair
+            .secret_key
+            .sign_with_seed("test seed".as_ref(), message.as_ref());
+        assert_eq!(Signature::from_bytes(sig.as_bytes()), Ok(sig));
+        // the padding is optional
+        let len = sig.encoded_len();
+        assert_eq!(Signature::from_bytes(&sig.as_bytes()[..len]), Ok(sig));
+
+        let mut bytes = sig.as_bytes().to_vec();
+        bytes[0] ^= 1;
+        assert_eq!(
+            Signature::from_bytes(bytes.as_ref()),
+            Err(FalconError::InvalidHeader {
+                expected: 0x30 + LOG_N as u8,
+                found: bytes[0],
+            })
+        );
+
+        // non-zero padding, a truncated body and an oversized input
+        let mut bytes = sig.as_bytes().to_vec();
+        bytes[SIG_LEN - 1] = 1;
+        assert_eq!(
+            Signature::from_bytes(bytes.as_ref()),
+            Err(FalconError::MalformedSignature)
+        );
+        assert_eq!(
+            Signature::from_bytes(&sig.as_bytes()[..len - 1]),
+            Err(FalconError::MalformedSignature)
+        );
+        let mut bytes = sig.as_bytes().to_vec();
+        bytes.push(0);
+        assert_eq!(
+            Signature::from_bytes(bytes.as_ref()),
+            Err(FalconError::MalformedSignature)
+        );
+        assert_eq!(
+            Signature::from_bytes(&[]),
+            Err(FalconError::MalformedSignature)
+        );
+    }
+
+    #[test]
+    fn test_sig_coefficients() {
+        let keypair = KeyPair::keygen();
+        for i in 0..10 {
+            let message = format!("testing message {}", i);
+            let sig = keypair.secret_key.sign(message.as_ref());
+
+            let coeffs = sig.coefficients();
+            assert_eq!(coeffs.len(), N);
+            assert_eq!(coeffs[..], DualPolynomial::from(&sig).to_signed()[..]);
+            assert_eq!(
+                sig.compressed_coefficients(),
+                &sig.as_bytes()[41..sig.encoded_len()]
+            );
+
+            assert_eq!(Signature::from_coefficients(sig.nonce(), &coeffs), Ok(sig));
+        }
+
+        // too many large coefficients to fit in SIG_LEN bytes
+        assert_eq!(
+            Signature::from_coefficients(&[0u8; 40], &[2047; N]),
+            Err(FalconError::MalformedSignature)
+        );
+    }
+
+    #[test]
+    fn test_encode_sig() {
+        let keypair = KeyPair::keygen();
+        for i in 0..10 {
+            let message = format!("testing message {}", i);
+            let sig = keypair.secret_key.sign(message.as_ref());
+            assert!(sig.is_canonical());
+
+            let coeffs = DualPolynomial::from(&sig).to_signed();
+            let mut encoded = vec![0x30 + LOG_N as u8];
+            encoded.extend_from_slice(&encode_sig(sig.nonce(), &coeffs));
+            assert_eq!(encoded[..], sig.as_bytes()[..encoded.len()]);
+            assert!(sig.as_bytes()[encoded.len()..].iter().all(|&x| x == 0));
+        }
+
+        // a non-zero padding is not canonical
+        let sig = keypair.secret_key.sign("testing message".as_ref());
+        let mut bad_sig = sig;
+        bad_sig.0[SIG_LEN - 1] = 1;
+        assert!(!bad_sig.is_canonical());
+        // neither is a wrong header
+        let mut bad_sig = sig;
+        bad_sig.0[0] ^= 1;
+        assert!(!bad_sig.is_canonical());
+    }
+
+    #[test]
+    fn test_signature_size_stats() {
+        let keypair = KeyPair::keygen();
+        let sigs: Vec<Signature> = (0..20)
+            .map(|i| {
+                let message = format!("testing message {}", i);
+                keypair.secret_key.sign(message.as_ref())
+            })
+            .collect();
+
+        for sig in sigs.iter() {
+            let coeffs = DualPolynomial::from(sig).to_signed();
+            assert_eq!(
+                sig.encoded_len(),
+                1 + encode_sig(sig.nonce(), &coeffs).len()
+            );
+        }
+
+        let stats = signature_size_stats(&sigs);
+        assert!(41 < stats.min);
+        assert!(stats.min <= stats.max);
+        assert!(stats.max <= SIG_LEN);
+        assert!(stats.min as f64 <= stats.mean);
+        assert!(stats.mean <= stats.max as f64);
+
+        let stats = signature_size_stats(&sigs[..1]);
+        assert_eq!(stats.min, sigs[0].encoded_len());
+        assert_eq!(stats.max, sigs[0].encoded_len());
+        assert_eq!(stats.mean, sigs[0].encoded_len() as f64);
     }
 }
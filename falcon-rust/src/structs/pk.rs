@@ -1,20 +1,154 @@
-use super::sig::Signature;
-use crate::{binder::*, param::*, DualPolynomial, NTTPolynomial, Polynomial};
+use super::{
+    sig::Signature,
+    timings::{Stopwatch, VerifyTimings},
+};
+#[cfg(feature = "ffi")]
+use crate::binder::*;
+use crate::{param::*, shake256_context, FalconError, MessageHasher, NTTPolynomial, Polynomial};
+#[cfg(feature = "ffi")]
 use libc::c_void;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct PublicKey(pub(crate) [u8; PK_LEN]);
 
+/// A public key together with its NTT form, computed once at construction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PreparedPublicKey {
+    pk: PublicKey,
+    pk_ntt: NTTPolynomial,
+}
+
+/// The outcome of a signature verification, together with the
+/// data it was derived from, for audit logging
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuditRecord {
+    /// SHAKE256 digest of the hash of the message polynomial,
+    /// in its `to_bytes` form
+    pub hm_fingerprint: [u8; 32],
+    /// square of the l2 norm of (sig, v)
+    pub l2_norm: u64,
+    /// whether the signature is accepted, i.e., l2_norm <= SIG_L2_BOUND
+    pub accepted: bool,
+}
+
+/// The decisions of the C and the Rust verifications of a signature,
+/// as returned by `cross_verify`
+#[cfg(feature = "ffi")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CrossVerifyResult {
+    /// the decision of `verify`
+    pub c_accepts: bool,
+    /// the decision of `verify_rust`; false if the signature does not
+    /// decode, as `verify_rust` panics then
+    pub rust_accepts: bool,
+    /// details of the signature, only when the two decisions disagree
+    pub diagnostics: Option<CrossVerifyDiagnostics>,
+}
+
+/// What `cross_verify` reports on a disagreement
+#[cfg(feature = "ffi")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CrossVerifyDiagnostics {
+    /// whether the signature passes `Signature::is_valid_encoding`
+    pub valid_encoding: bool,
+    /// square of the l2 norm of (sig, v); None if the signature does
+    /// not decode
+    pub l2_norm: Option<u64>,
+}
+
+#[cfg(feature = "ffi")]
+impl CrossVerifyResult {
+    /// whether the C and the Rust verifications agree
+    pub fn agree(&self) -> bool {
+        self.c_accepts == self.rust_accepts
+    }
+}
+
 impl PublicKey {
     /// Expose the public key as a byte string
     pub fn as_bytes(&self) -> &[u8] {
         self.0.as_ref()
     }
 
+    /// Parse a public key from its encoding, i.e., a header byte followed
+    /// by the coefficients of h on 14 bits each.
+    /// Only the canonical encoding is accepted: the header has to match
+    /// the parameter set, and re-encoding the decoded coefficients has
+    /// to give back the input.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FalconError> {
+        match bytes.first() {
+            None => return Err(FalconError::MalformedPublicKey),
+            Some(&header) if header != LOG_N as u8 => {
+                return Err(FalconError::InvalidHeader {
+                    expected: LOG_N as u8,
+                    found: header,
+                })
+            },
+            _ => (),
+        }
+        if bytes.len() != PK_LEN {
+            return Err(FalconError::MalformedPublicKey);
+        }
+
+        let coeff = try_mod_q_decode(bytes[1..].as_ref()).ok_or(FalconError::MalformedPublicKey)?;
+        if mod_q_encode(&coeff) != bytes[1..] {
+            return Err(FalconError::MalformedPublicKey);
+        }
+
+        let mut pk = [0u8; PK_LEN];
+        pk.copy_from_slice(bytes);
+        Ok(Self(pk))
+    }
+
+    /// The encoding of the public key in lowercase hex
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.as_bytes())
+    }
+
+    /// Parse a public key from the hex string of its encoding;
+    /// see `from_bytes`.
+    pub fn from_hex(s: &str) -> Result<Self, FalconError> {
+        let bytes = hex::decode(s).map_err(|_| FalconError::MalformedPublicKey)?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// The encoding of the public key in standard, padded base64
+    pub fn to_base64(&self) -> String {
+        base64::encode(self.as_bytes())
+    }
+
+    /// Parse a public key from the standard, padded base64 string of
+    /// its encoding; see `from_bytes`.
+    pub fn from_base64(s: &str) -> Result<Self, FalconError> {
+        let bytes = base64::decode(s).map_err(|_| FalconError::MalformedPublicKey)?;
+        Self::from_bytes(&bytes)
+    }
+
     /// verification using C wrapper
+    #[cfg(feature = "ffi")]
     pub fn verify(&self, message: &[u8], sig: &Signature) -> bool {
-        let sig_type = 2;
         let mut buf = [0u8; VERIFY_BUF_LEN];
+        self.verify_with_buffer(message, sig, buf.as_mut())
+    }
+
+    /// Same as `verify`, but with a caller provided scratch buffer for
+    /// the C implementation, so that it can be reused across calls.
+    /// Panics if buf is shorter than `VERIFY_BUF_LEN`.
+    #[cfg(feature = "ffi")]
+    pub fn verify_with_buffer(&self, message: &[u8], sig: &Signature, buf: &mut [u8]) -> bool {
+        assert!(
+            buf.len() >= VERIFY_BUF_LEN,
+            "Invalid buffer length: {}",
+            buf.len()
+        );
+        // reject malformed signatures without crossing the FFI boundary
+        if !sig.is_valid_encoding() {
+            return false;
+        }
+
+        let sig_type = 2;
 
         let res = unsafe {
             falcon_verify(
@@ -26,6 +160,43 @@ impl PublicKey {
                 message.as_ptr() as *const c_void,
                 message.len() as u64,
                 buf.as_mut_ptr() as *mut c_void,
+                buf.len() as u64,
+            )
+        };
+
+        res == 0
+    }
+
+    /// verification of a signature on a message with associated data,
+    /// using C wrapper
+    #[cfg(feature = "ffi")]
+    pub fn verify_with_aad(&self, aad: &[u8], message: &[u8], sig: &Signature) -> bool {
+        let sig_type = 2;
+        let mut hash_data = shake256_context::init();
+        let mut buf = [0u8; VERIFY_BUF_LEN];
+
+        if unsafe {
+            falcon_verify_start(
+                &mut hash_data as *mut shake256_context,
+                sig.0.as_ptr() as *const c_void,
+                sig.0.len() as u64,
+            )
+        } != 0
+        {
+            return false;
+        }
+        hash_data.inject_aad(aad);
+        hash_data.inject(message);
+
+        let res = unsafe {
+            falcon_verify_finish(
+                sig.0.as_ptr() as *const c_void,
+                sig.0.len() as u64,
+                sig_type,
+                self.0.as_ptr() as *const c_void,
+                self.0.len() as u64,
+                &mut hash_data as *mut shake256_context,
+                buf.as_mut_ptr() as *mut c_void,
                 VERIFY_BUF_LEN as u64,
             )
         };
@@ -40,6 +211,19 @@ impl PublicKey {
         mod_q_decode(self.0[1..].as_ref())
     }
 
+    /// Convert the public key into its NTT form
+    pub fn to_ntt(&self) -> NTTPolynomial {
+        (&Polynomial(self.unpack())).into()
+    }
+
+    /// Precompute the NTT form of the public key for repeated verification
+    pub fn prepare(&self) -> PreparedPublicKey {
+        PreparedPublicKey {
+            pk: *self,
+            pk_ntt: self.to_ntt(),
+        }
+    }
+
     // using rust's functions to check the validity of a signature
     pub fn verify_rust(&self, message: &[u8], sig: &Signature) -> bool {
         let pk: Polynomial = self.into();
@@ -54,21 +238,131 @@ impl PublicKey {
         l2_norm <= SIG_L2_BOUND
     }
 
-    // check the validity of a signature via the parsed method
-    // this is slow; but will improve circuit complexity for ZKP
-    pub fn verify_parsed_sig(&self, message: &[u8], sig: &Signature) -> bool {
+    /// Same as `verify_rust`, for a signature on a message with associated
+    /// data; the pure Rust counterpart of `verify_with_aad`.
+    pub fn verify_rust_with_aad(&self, aad: &[u8], message: &[u8], sig: &Signature) -> bool {
         let pk: Polynomial = self.into();
-        let sig_u: DualPolynomial = sig.into();
-        let hm = Polynomial::from_hash_of_message(message, sig.0[1..41].as_ref());
+        let sig_u: Polynomial = sig.into();
+        let mut hasher = MessageHasher::new(sig.nonce());
+        hasher.update_aad(aad);
+        hasher.update(message);
+        let hm = hasher.finalize();
 
         // compute v = hm - uh
-        let uh_pos = sig_u.pos * pk;
-        let uh_neg = sig_u.neg * pk;
-        let v = hm - uh_pos + uh_neg;
+        let v = hm - sig_u * pk;
 
         let l2_norm = sig_u.l2_norm() + v.l2_norm();
         l2_norm <= SIG_L2_BOUND
     }
+
+    /// Recover v = hm - sig * pk mod q, the second half of the short
+    /// vector (sig, v) of a signature; this is the witness v of the
+    /// verification circuits.
+    pub fn recover_v(&self, message: &[u8], sig: &Signature) -> Polynomial {
+        let pk: Polynomial = self.into();
+        let sig_u: Polynomial = sig.into();
+        let hm = Polynomial::from_hash_of_message(message, sig.nonce());
+        hm - sig_u * pk
+    }
+
+    /// Same as `verify_rust`, but with a caller supplied bound on the
+    /// square of the l2 norm instead of `SIG_L2_BOUND`, for experimenting
+    /// with the acceptance threshold. NOT a secure verification unless
+    /// bound is SIG_L2_BOUND.
+    pub fn verify_rust_with_bound(&self, message: &[u8], sig: &Signature, bound: u64) -> bool {
+        let sig_u: Polynomial = sig.into();
+        let v = self.recover_v(message, sig);
+        sig_u.l2_norm() + v.l2_norm() <= bound
+    }
+
+    /// Same as `verify_rust`, but returns how far below the norm bound
+    /// the signature is: Some(SIG_L2_BOUND - l2_norm) for a valid
+    /// signature, and None otherwise.
+    pub fn verify_with_margin(&self, message: &[u8], sig: &Signature) -> Option<u64> {
+        let sig_u: Polynomial = sig.into();
+        let v = self.recover_v(message, sig);
+        SIG_L2_BOUND.checked_sub(sig_u.l2_norm() + v.l2_norm())
+    }
+
+    /// Same as `verify_rust`, but also returns the time spent in each
+    /// phase of the verification, for profiling.
+    /// The timings are only measured with the `profiling` feature.
+    pub fn verify_timed(&self, message: &[u8], sig: &Signature) -> (bool, VerifyTimings) {
+        let mut total = Stopwatch::start();
+        let mut stopwatch = Stopwatch::start();
+        let mut timings = VerifyTimings::default();
+
+        let pk: Polynomial = self.into();
+        let sig_u: Polynomial = sig.into();
+        let hm = Polynomial::from_hash_of_message(message, sig.nonce());
+        timings.hash_to_point = stopwatch.lap();
+
+        let uh_ntt = NTTPolynomial::from(&sig_u) * NTTPolynomial::from(&pk);
+        timings.ntt_mul = stopwatch.lap();
+
+        let uh = Polynomial::from(&uh_ntt);
+        timings.inv_ntt = stopwatch.lap();
+
+        // compute v = hm - uh
+        let v = hm - uh;
+        let l2_norm = sig_u.l2_norm() + v.l2_norm();
+        timings.norm = stopwatch.lap();
+
+        timings.total = total.lap();
+        (l2_norm <= SIG_L2_BOUND, timings)
+    }
+
+    /// Same as `verify_rust`, but additionally rejects signatures whose
+    /// u part is the zero polynomial. An honest signer never produces one,
+    /// and for such a signature v = hm, so it would only be accepted
+    /// because of a short hm.
+    pub fn verify_rust_strict(&self, message: &[u8], sig: &Signature) -> bool {
+        let sig_u: Polynomial = sig.into();
+        if sig_u == Polynomial::default() {
+            return false;
+        }
+        self.verify_rust(message, sig)
+    }
+
+    /// Same as `verify_rust`, but returns an audit record of the
+    /// verification rather than only the decision.
+    /// Returns an error if the signature is not a valid encoding.
+    pub fn verify_rust_audit(
+        &self,
+        message: &[u8],
+        sig: &Signature,
+    ) -> Result<AuditRecord, FalconError> {
+        if !sig.is_valid_encoding() {
+            return Err(FalconError::MalformedSignature);
+        }
+
+        let pk: Polynomial = self.into();
+        let sig_u: Polynomial = sig.into();
+        let hm = Polynomial::from_hash_of_message(message, sig.nonce());
+
+        // compute v = hm - uh
+        let uh = sig_u * pk;
+        let v = hm - uh;
+        let l2_norm = sig_u.l2_norm() + v.l2_norm();
+
+        let mut hasher = shake256_context::init();
+        hasher.inject(hm.to_bytes().as_ref());
+        hasher.finalize();
+        let mut hm_fingerprint = [0u8; 32];
+        hm_fingerprint.copy_from_slice(hasher.extract(32).as_ref());
+
+        Ok(AuditRecord {
+            hm_fingerprint,
+            l2_norm,
+            accepted: l2_norm <= SIG_L2_BOUND,
+        })
+    }
+
+    // check the validity of a signature via the parsed method
+    // this is slow; but will improve circuit complexity for ZKP
+    pub fn verify_parsed_sig(&self, message: &[u8], sig: &Signature) -> bool {
+        sig.parse_cached().verify_against(self, message)
+    }
 }
 
 impl From<&PublicKey> for Polynomial {
@@ -79,14 +373,118 @@ impl From<&PublicKey> for Polynomial {
 
 impl From<&PublicKey> for NTTPolynomial {
     fn from(pk: &PublicKey) -> Self {
-        (&Polynomial(pk.unpack())).into()
+        pk.to_ntt()
+    }
+}
+
+impl From<&PublicKey> for PreparedPublicKey {
+    fn from(pk: &PublicKey) -> Self {
+        pk.prepare()
+    }
+}
+
+impl PreparedPublicKey {
+    /// Access the underlying public key
+    pub fn public_key(&self) -> &PublicKey {
+        &self.pk
+    }
+
+    /// Access the cached NTT form of the public key
+    pub fn ntt(&self) -> &NTTPolynomial {
+        &self.pk_ntt
+    }
+
+    /// using rust's functions to check the validity of a signature,
+    /// reusing the cached NTT form of the public key
+    pub fn verify_rust(&self, message: &[u8], sig: &Signature) -> bool {
+        verify_with_pk_ntt(&self.pk_ntt, message, sig)
+    }
+}
+
+/// using rust's functions to check the validity of a signature,
+/// for a public key given in its NTT form rather than packed
+pub fn verify_with_pk_ntt(pk_ntt: &NTTPolynomial, message: &[u8], sig: &Signature) -> bool {
+    let sig_u: Polynomial = sig.into();
+    let sig_u_ntt: NTTPolynomial = (&sig_u).into();
+    let hm = Polynomial::from_hash_of_message(message, sig.nonce());
+
+    // compute v = hm - uh
+    let uh: Polynomial = (&(sig_u_ntt * *pk_ntt)).into();
+    let v = hm - uh;
+
+    let l2_norm = sig_u.l2_norm() + v.l2_norm();
+    l2_norm <= SIG_L2_BOUND
+}
+
+/// Verify a signature with both `verify` and `verify_rust`, for debugging
+/// the parity of the C and the Rust implementations; on a disagreement,
+/// the result also holds the norm and decoding status of the signature.
+#[cfg(feature = "ffi")]
+pub fn cross_verify(pk: &PublicKey, message: &[u8], sig: &Signature) -> CrossVerifyResult {
+    let c_accepts = pk.verify(message, sig);
+    let decodes = sig.try_unpack().is_ok();
+    let rust_accepts = decodes && pk.verify_rust(message, sig);
+
+    let diagnostics = if c_accepts != rust_accepts {
+        let l2_norm = if decodes {
+            let sig_u: Polynomial = sig.into();
+            Some(sig_u.l2_norm() + pk.recover_v(message, sig).l2_norm())
+        } else {
+            None
+        };
+        Some(CrossVerifyDiagnostics {
+            valid_encoding: sig.is_valid_encoding(),
+            l2_norm,
+        })
+    } else {
+        None
+    };
+
+    CrossVerifyResult {
+        c_accepts,
+        rust_accepts,
+        diagnostics,
     }
 }
 
+/// Verify a batch of independent (public key, message, signature) tuples
+/// with `verify_rust`, one after the other; the i-th output is the result
+/// for the i-th tuple.
+pub fn verify_batch_rust(batch: &[(&PublicKey, &[u8], &Signature)]) -> Vec<bool> {
+    batch
+        .iter()
+        .map(|(pk, message, sig)| pk.verify_rust(message, sig))
+        .collect()
+}
+
+/// Same as `verify_batch_rust`, with the tuples verified across threads
+#[cfg(feature = "parallel")]
+pub fn verify_batch_rust_parallel(batch: &[(&PublicKey, &[u8], &Signature)]) -> Vec<bool> {
+    batch
+        .par_iter()
+        .map(|(pk, message, sig)| pk.verify_rust(message, sig))
+        .collect()
+}
+
+// the length of the N coefficients encoded on 14 bits each
+const ENCODED_COEFFS_LEN: usize = (N * 14 + 7) / 8;
+
 fn mod_q_decode(input: &[u8]) -> [u16; N] {
-    if input.len() != (N * 14 + 7) / 8 {
+    if input.len() != ENCODED_COEFFS_LEN {
         panic!("incorrect input length")
     }
+    match try_mod_q_decode(input) {
+        Some(output) => output,
+        None => panic!("incorrect input"),
+    }
+}
+
+/// Decode the coefficients of a public key.
+/// Returns None if the input is not a valid encoding.
+fn try_mod_q_decode(input: &[u8]) -> Option<[u16; N]> {
+    if input.len() != ENCODED_COEFFS_LEN {
+        return None;
+    }
 
     let mut input_pt = 0;
     let mut acc = 0u32;
@@ -103,14 +501,38 @@ fn mod_q_decode(input: &[u8]) -> [u16; N] {
         if acc_len >= 14 {
             acc_len -= 14;
             let w = (acc >> acc_len) & 0x3FFF;
-            assert!(w < 12289, "incorrect input: {}", w);
+            if w >= MODULUS as u32 {
+                return None;
+            }
             output[output_ptr] = w as u16;
             output_ptr += 1;
         }
     }
 
     if (acc & ((1u32 << acc_len) - 1)) != 0 {
-        panic!("incorrect remaining data")
+        return None;
+    }
+
+    Some(output)
+}
+
+/// Encode the coefficients of a public key on 14 bits each;
+/// the inverse of `try_mod_q_decode`
+fn mod_q_encode(input: &[u16; N]) -> Vec<u8> {
+    let mut output = Vec::with_capacity(ENCODED_COEFFS_LEN);
+    let mut acc = 0u32;
+    let mut acc_len = 0;
+
+    for &w in input.iter() {
+        acc = (acc << 14) | w as u32;
+        acc_len += 14;
+        while acc_len >= 8 {
+            acc_len -= 8;
+            output.push((acc >> acc_len) as u8);
+        }
+    }
+    if acc_len > 0 {
+        output.push((acc << (8 - acc_len)) as u8);
     }
 
     output
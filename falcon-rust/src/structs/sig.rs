@@ -1,20 +1,279 @@
-use crate::{DualPolynomial, Polynomial, MODULUS, MODULUS_MINUS_1_OVER_TWO, N, SIG_LEN};
+use super::PublicKey;
+use crate::{
+    DualPolynomial, FalconError, ParameterSet, Polynomial, LOG_N, MODULUS, MODULUS_MINUS_1_OVER_TWO, N,
+    SIG_L2_BOUND, SIG_LEN,
+};
+
+// the header byte of a compressed signature: the high nibble is the
+// format, the low nibble is LOG_N
+const SIG_HEADER: u8 = 0x30 + LOG_N as u8;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Signature(pub(crate) [u8; SIG_LEN]);
 
+/// A signature that has already been decoded into its dual polynomial form.
+/// Useful when the same signature is checked against multiple keys.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParsedSignature {
+    nonce: [u8; 40],
+    sig_u: DualPolynomial,
+}
+
+/// Statistics of the encoded lengths of a batch of signatures,
+/// i.e., their lengths before the zero padding to `SIG_LEN`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SizeStats {
+    pub min: usize,
+    pub max: usize,
+    pub mean: f64,
+}
+
+/// Compute the statistics of the encoded lengths of the signatures.
+/// Panics if sigs is empty, or if any of the signatures
+/// is not a valid encoding.
+pub fn signature_size_stats(sigs: &[Signature]) -> SizeStats {
+    assert!(!sigs.is_empty(), "no signature");
+
+    let lens: Vec<usize> = sigs.iter().map(|sig| sig.encoded_len()).collect();
+    SizeStats {
+        min: *lens.iter().min().unwrap(),
+        max: *lens.iter().max().unwrap(),
+        mean: lens.iter().sum::<usize>() as f64 / lens.len() as f64,
+    }
+}
+
 impl Signature {
     /// Unpack the signature into a vector of integers
     /// within the range of [0, MODULUS)
+    /// Panics if the header byte or the body is malformed.
     pub fn unpack(&self) -> [u16; N] {
-        let res = comp_decode(self.0[41..].as_ref());
-        res
+        match self.try_unpack() {
+            Ok(res) => res,
+            Err(e) => panic!("incorrect input: {}", e),
+        }
+    }
+
+    /// Same as `unpack`, but returns an error instead of panicking.
+    /// The header byte is checked first, against the compressed format
+    /// and the parameter set, so that the body of a signature of another
+    /// format or degree is never decoded.
+    pub fn try_unpack(&self) -> Result<[u16; N], FalconError> {
+        check_header(self.0[0])?;
+        try_comp_decode(self.0[41..].as_ref())
+            .map(|(res, _)| res)
+            .ok_or(FalconError::MalformedSignature)
+    }
+
+    /// Expose the signature as a byte string
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_ref()
     }
 
     /// return the nonce component of the signature
     pub fn nonce(&self) -> &[u8] {
         self.0[1..41].as_ref()
     }
+
+    /// The parameter set given by the header byte of the signature;
+    /// None if the header is not that of a compressed signature.
+    pub fn parameter_set(&self) -> Option<ParameterSet> {
+        ParameterSet::from_sig_header(self.0[0])
+    }
+
+    /// Parse a signature from its encoding, with or without the zero
+    /// padding to `SIG_LEN`: a header byte, the 40-byte nonce and the
+    /// compressed coefficients. The padded result has to pass
+    /// `is_valid_encoding`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FalconError> {
+        match bytes.first() {
+            None => return Err(FalconError::MalformedSignature),
+            Some(&header) => check_header(header)?,
+        }
+        if bytes.len() > SIG_LEN {
+            return Err(FalconError::MalformedSignature);
+        }
+
+        let mut sig = Self([0u8; SIG_LEN]);
+        sig.0[..bytes.len()].copy_from_slice(bytes);
+        if !sig.is_valid_encoding() {
+            return Err(FalconError::MalformedSignature);
+        }
+        Ok(sig)
+    }
+
+    /// The padded encoding of the signature in lowercase hex
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.as_bytes())
+    }
+
+    /// Parse a signature from the hex string of its encoding, with or
+    /// without the zero padding; see `from_bytes`.
+    pub fn from_hex(s: &str) -> Result<Self, FalconError> {
+        let bytes = hex::decode(s).map_err(|_| FalconError::MalformedSignature)?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// The padded encoding of the signature in standard, padded base64
+    pub fn to_base64(&self) -> String {
+        base64::encode(self.as_bytes())
+    }
+
+    /// Parse a signature from the standard, padded base64 string of its
+    /// encoding, with or without the zero padding; see `from_bytes`.
+    pub fn from_base64(s: &str) -> Result<Self, FalconError> {
+        let bytes = base64::decode(s).map_err(|_| FalconError::MalformedSignature)?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// The coefficients of the signature, in the centered
+    /// representation (-MODULUS/2, MODULUS/2); of length N.
+    /// Panics if the body is not a valid encoding.
+    pub fn coefficients(&self) -> Vec<i16> {
+        Polynomial::from(self).to_centered().to_vec()
+    }
+
+    /// The compressed coefficients, without the header, the nonce
+    /// and the zero padding.
+    /// Panics if the body is not a valid encoding.
+    pub fn compressed_coefficients(&self) -> &[u8] {
+        self.0[41..self.encoded_len()].as_ref()
+    }
+
+    /// Build a signature from a nonce and its centered coefficients;
+    /// the inverse of `nonce` and `coefficients`.
+    /// Panics if the nonce is not 40 bytes, if coeffs is not of length
+    /// N, or if a coefficient is not in (-2048, 2048). Returns an error
+    /// if the compressed coefficients do not fit in `SIG_LEN` bytes.
+    pub fn from_coefficients(nonce: &[u8], coeffs: &[i16]) -> Result<Self, FalconError> {
+        assert_eq!(nonce.len(), 40, "Invalid nonce length: {}", nonce.len());
+        assert_eq!(coeffs.len(), N, "Invalid input length: {}", coeffs.len());
+        let mut array = [0i16; N];
+        array.copy_from_slice(coeffs);
+
+        let mut bytes = vec![SIG_HEADER];
+        bytes.extend_from_slice(&encode_sig(nonce, &array));
+        Self::from_bytes(&bytes)
+    }
+
+    /// The length of the signature without its zero padding:
+    /// the header, the nonce and the compressed coefficients.
+    /// Panics if the body is not a valid encoding.
+    pub fn encoded_len(&self) -> usize {
+        match try_comp_decode(self.0[41..].as_ref()) {
+            Some((_, len)) => 41 + len,
+            None => panic!("incorrect input"),
+        }
+    }
+
+    /// Check that the signature is the canonical encoding of its
+    /// (nonce, coefficients): re-encoding the decoded coefficients
+    /// gives back the signature, up to the zero padding.
+    /// This rejects malleated encodings of the same signature.
+    pub fn is_canonical(&self) -> bool {
+        if check_header(self.0[0]).is_err() {
+            return false;
+        }
+        let coeff = match try_comp_decode(self.0[41..].as_ref()) {
+            Some((coeff, _)) => coeff,
+            None => return false,
+        };
+
+        let signed = Polynomial(coeff).to_centered();
+
+        let encoded = encode_sig(self.nonce(), &signed);
+        encoded.len() < SIG_LEN
+            && encoded[..] == self.0[1..1 + encoded.len()]
+            && self.0[1 + encoded.len()..].iter().all(|&x| x == 0)
+    }
+
+    /// Structural check of the padded signature encoding, without
+    /// any arithmetic: the header byte matches the parameter set,
+    /// the body decodes, and the padding after it is all zeros.
+    pub fn is_valid_encoding(&self) -> bool {
+        if check_header(self.0[0]).is_err() {
+            return false;
+        }
+        match try_comp_decode(self.0[41..].as_ref()) {
+            Some((_, len)) => self.0[41 + len..].iter().all(|&x| x == 0),
+            None => false,
+        }
+    }
+
+    /// Decode the signature once into a form that can be verified
+    /// repeatedly without re-parsing
+    pub fn parse_cached(&self) -> ParsedSignature {
+        let mut nonce = [0u8; 40];
+        nonce.copy_from_slice(self.nonce());
+        ParsedSignature {
+            nonce,
+            sig_u: self.into(),
+        }
+    }
+
+    /// A copy of a valid signature with one coefficient inflated to
+    /// the largest magnitude the encoding still fits, so that
+    /// l2_norm(sig | hm - sig * pk) > SIG_L2_BOUND while the encoding
+    /// stays well formed. For negative tests of the verifiers and the
+    /// verification circuits.
+    /// Panics if no such coefficient is found.
+    #[cfg(any(test, feature = "testing"))]
+    pub fn over_norm_for_testing(&self, pk: &PublicKey, message: &[u8]) -> Self {
+        let hm = Polynomial::from_hash_of_message(message, self.nonce());
+        let pk_poly: Polynomial = pk.into();
+        let coeffs = self.coefficients();
+
+        for i in 0..N {
+            // each step of 128 saves one bit of the compressed encoding,
+            // in case the signature has little padding left
+            let mut magnitude = 2047;
+            while magnitude > coeffs[i].abs() {
+                let mut inflated = coeffs.clone();
+                inflated[i] = if coeffs[i] < 0 { -magnitude } else { magnitude };
+                let sig = match Self::from_coefficients(self.nonce(), &inflated) {
+                    Ok(sig) => sig,
+                    // the compressed coefficients no longer fit
+                    Err(_) => {
+                        magnitude -= 128;
+                        continue;
+                    }
+                };
+
+                let sig_poly: Polynomial = (&sig).into();
+                let v = hm - sig_poly * pk_poly;
+                if sig_poly.l2_norm() + v.l2_norm() > SIG_L2_BOUND {
+                    return sig;
+                }
+                break;
+            }
+        }
+        panic!("failed to inflate the signature")
+    }
+}
+
+impl ParsedSignature {
+    /// return the nonce component of the signature
+    pub fn nonce(&self) -> &[u8] {
+        self.nonce.as_ref()
+    }
+
+    /// return the signature in its dual polynomial form
+    pub fn dual_poly(&self) -> &DualPolynomial {
+        &self.sig_u
+    }
+
+    /// check the validity of the parsed signature for a public key
+    pub fn verify_against(&self, pk: &PublicKey, message: &[u8]) -> bool {
+        let pk: Polynomial = pk.into();
+        let hm = Polynomial::from_hash_of_message(message, self.nonce.as_ref());
+
+        // compute v = hm - uh
+        let uh_pos = self.sig_u.pos * pk;
+        let uh_neg = self.sig_u.neg * pk;
+        let v = hm - uh_pos + uh_neg;
+
+        let l2_norm = self.sig_u.l2_norm() + v.l2_norm();
+        l2_norm <= SIG_L2_BOUND
+    }
 }
 
 impl From<&Signature> for Polynomial {
@@ -47,7 +306,58 @@ impl From<&Signature> for DualPolynomial {
     }
 }
 
-fn comp_decode(input: &[u8]) -> [u16; N] {
+/// Encode a signature without its header byte: the nonce followed by
+/// the compressed coefficients; the inverse of the decoding of
+/// `Signature`. The output is not padded.
+/// Panics if any coefficient is not in (-2048, 2048), which the
+/// compressed format cannot represent.
+pub fn encode_sig(nonce: &[u8], coeffs: &[i16; N]) -> Vec<u8> {
+    let mut output = nonce.to_vec();
+    let mut acc = 0u32;
+    let mut acc_len = 0;
+
+    for &t in coeffs.iter() {
+        // sign bit and the low seven bits of the absolute value
+        let w = t.unsigned_abs() as u32;
+        assert!(w < 2048, "Invalid input: {}", t);
+        acc = (acc << 1) | (t < 0) as u32;
+        acc = (acc << 7) | (w & 127);
+
+        // the high bits of the absolute value, in unary
+        let high = w >> 7;
+        acc <<= high + 1;
+        acc |= 1;
+        acc_len += 8 + high + 1;
+
+        while acc_len >= 8 {
+            acc_len -= 8;
+            output.push((acc >> acc_len) as u8);
+        }
+    }
+
+    // the unused bits of the last byte are zero
+    if acc_len > 0 {
+        output.push((acc << (8 - acc_len)) as u8);
+    }
+
+    output
+}
+
+fn check_header(header: u8) -> Result<(), FalconError> {
+    if header == SIG_HEADER {
+        Ok(())
+    } else {
+        Err(FalconError::InvalidHeader {
+            expected: SIG_HEADER,
+            found: header,
+        })
+    }
+}
+
+/// Decode a compressed signature body.
+/// Returns the coefficients and the number of bytes consumed,
+/// or None if the input is not a valid encoding.
+fn try_comp_decode(input: &[u8]) -> Option<([u16; N], usize)> {
     let mut input_pt = 0;
     let mut acc = 0u32;
     let mut acc_len = 0;
@@ -57,7 +367,7 @@ fn comp_decode(input: &[u8]) -> [u16; N] {
         // Get next eight bits: sign and low seven bits of the
         // absolute value.
 
-        acc = (acc << 8) | (input[input_pt] as u32);
+        acc = (acc << 8) | (*input.get(input_pt)? as u32);
         input_pt += 1;
         let b = acc >> acc_len;
         let s = b & 128;
@@ -67,7 +377,7 @@ fn comp_decode(input: &[u8]) -> [u16; N] {
 
         loop {
             if acc_len == 0 {
-                acc = (acc << 8) | (input[input_pt] as u32);
+                acc = (acc << 8) | (*input.get(input_pt)? as u32);
                 input_pt += 1;
                 acc_len = 8;
             }
@@ -76,11 +386,14 @@ fn comp_decode(input: &[u8]) -> [u16; N] {
                 break;
             }
             m += 128;
-            assert!(m < 2048, "incorrect input: {}", m);
+            if m >= 2048 {
+                return None;
+            }
         }
 
+        // "-0" is forbidden
         if s != 0 && m == 0 {
-            panic!("incorrect remaining data")
+            return None;
         }
         *e = if s != 0 {
             (MODULUS as u32 - m) as u16
@@ -91,8 +404,8 @@ fn comp_decode(input: &[u8]) -> [u16; N] {
 
     // Unused bits in the last byte must be zero.
     if (acc & ((1 << acc_len) - 1)) != 0 {
-        panic!("incorrect remaining data")
+        return None;
     }
 
-    output
+    Some((output, input_pt))
 }
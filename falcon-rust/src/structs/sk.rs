@@ -1,36 +1,95 @@
-use crate::{binder::*, param::*};
+use crate::{binder::*, param::*, FalconError, Polynomial};
 use libc::c_void;
 use rand_chacha::{
     rand_core::{RngCore, SeedableRng},
     ChaCha20Rng,
 };
-use zeroize::Zeroize;
+use std::fmt;
+use zeroize::Zeroizing;
 
 use super::{PublicKey, Signature};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// The domain separation tag of `SecretKey::sign_prehashed`
+pub const PREHASH_DOMAIN: &[u8] = b"falcon-rust prehashed digest\0";
+
+/// The message actually signed by `SecretKey::sign_prehashed` for a digest,
+/// i.e., `PREHASH_DOMAIN || digest`; a prehashed signature is verified as
+/// a signature on this message.
+pub fn prehashed_message(digest: &[u8; 64]) -> Vec<u8> {
+    [PREHASH_DOMAIN, digest.as_ref()].concat()
+}
+
+#[derive(Clone, Copy, PartialEq)]
 pub struct SecretKey(pub(crate) [u8; SK_LEN]);
 
+// The key bytes are never printed, so that a debug print of a struct
+// holding the key, e.g. a `KeyPair`, does not leak it.
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SecretKey([REDACTED; {}])", SK_LEN)
+    }
+}
+
 impl SecretKey {
+    /// Expose the secret key as a byte string
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+
+    /// Parse a secret key from its encoding, i.e., a header byte followed
+    /// by the compressed f, g and F.
+    /// The input is rejected unless the header matches the parameter set
+    /// and a public key can be recovered from it.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FalconError> {
+        match bytes.first() {
+            None => return Err(FalconError::MalformedSecretKey),
+            Some(&header) if header != 0x50 + LOG_N as u8 => {
+                return Err(FalconError::InvalidHeader {
+                    expected: 0x50 + LOG_N as u8,
+                    found: header,
+                })
+            },
+            _ => (),
+        }
+        if bytes.len() != SK_LEN {
+            return Err(FalconError::MalformedSecretKey);
+        }
+
+        let mut sk = [0u8; SK_LEN];
+        sk.copy_from_slice(bytes);
+        let sk = Self(sk);
+        sk.try_make_public_key()
+            .ok_or(FalconError::MalformedSecretKey)?;
+        Ok(sk)
+    }
+
     /// Recover the public key from the secret key
     pub fn make_public_key(&self) -> PublicKey {
+        self.try_make_public_key().unwrap()
+    }
+
+    /// Recover the public key from the secret key.
+    /// Returns None if the secret key is malformed.
+    pub(crate) fn try_make_public_key(&self) -> Option<PublicKey> {
         let mut pk = [0u8; PK_LEN];
-        let mut buf = [0u8; MAKE_PK_BUF_LEN];
+        // wiped when dropped, on every exit path including a panic
+        let mut buf = Zeroizing::new(vec![0u8; MAKE_PK_BUF_LEN]);
 
-        unsafe {
-            assert!(
-                falcon_make_public(
-                    pk.as_mut_ptr() as *mut c_void,
-                    PK_LEN as u64,
-                    self.0.as_ptr() as *const c_void,
-                    SK_LEN as u64,
-                    buf.as_mut_ptr() as *mut c_void,
-                    MAKE_PK_BUF_LEN as u64
-                ) == 0
+        let res = unsafe {
+            falcon_make_public(
+                pk.as_mut_ptr() as *mut c_void,
+                PK_LEN as u64,
+                self.0.as_ptr() as *const c_void,
+                SK_LEN as u64,
+                buf.as_mut_ptr() as *mut c_void,
+                MAKE_PK_BUF_LEN as u64,
             )
+        };
+        if res == 0 {
+            Some(PublicKey(pk))
+        } else {
+            None
         }
-        buf.zeroize();
-        PublicKey(pk)
     }
 
     /// Sign a message with a secret key and a seed.
@@ -44,29 +103,118 @@ impl SecretKey {
 
     /// Sign a message with a secret key and a seed.
     pub fn sign_with_seed(&self, seed: &[u8], message: &[u8]) -> Signature {
+        self.try_sign_with_seed(seed, message).unwrap()
+    }
+
+    /// Sign the 64 bytes digest of a large object, in a hash-then-sign
+    /// scheme, instead of the object itself.
+    /// The digest is hashed to a point after the `PREHASH_DOMAIN` tag, so
+    /// that the signature does not verify as a `sign` signature on the bare
+    /// digest; it does verify as one on `prehashed_message(digest)`, so
+    /// messages starting with the tag should not be signed with `sign`.
+    pub fn sign_prehashed(&self, digest: &[u8; 64], seed: &[u8]) -> Signature {
+        self.sign_with_seed(seed, &prehashed_message(digest))
+    }
+
+    /// Sign a message with a seed, and also return the witness
+    /// v = hm - sig * pk of the signature, for a prover that needs it.
+    /// pk is the public key of self; it is not re-derived.
+    pub fn sign_with_witness(
+        &self,
+        seed: &[u8],
+        message: &[u8],
+        pk: &PublicKey,
+    ) -> (Signature, Polynomial) {
+        let sig = self.sign_with_seed(seed, message);
+        let v = pk.recover_v(message, &sig);
+        (sig, v)
+    }
+
+    /// Sign a message with a secret key and a seed.
+    /// Returns None if the secret key is malformed.
+    pub(crate) fn try_sign_with_seed(&self, seed: &[u8], message: &[u8]) -> Option<Signature> {
+        let mut shake256_context = shake256_context::init_with_seed(seed);
+        let mut sig = [0u8; SIG_LEN];
+        let sig_len = &mut (SIG_LEN as u64);
+        let sig_type = 2;
+        let mut buf = Zeroizing::new(vec![0u8; SIGN_BUF_LEN]);
+
+        let res = unsafe {
+            falcon_sign_dyn(
+                &mut shake256_context as *mut shake256_context,
+                sig.as_mut_ptr() as *mut c_void,
+                sig_len as *mut u64,
+                sig_type,
+                self.0.as_ptr() as *const c_void,
+                SK_LEN as u64,
+                message.as_ptr() as *const c_void,
+                message.len() as u64,
+                buf.as_mut_ptr() as *mut c_void,
+                SIGN_BUF_LEN as u64,
+            )
+        };
+        if res == 0 {
+            Some(Signature(sig))
+        } else {
+            None
+        }
+    }
+
+    /// Sign a message together with associated data.
+    /// The associated data is authenticated by the signature
+    /// but is not part of the message.
+    pub fn sign_with_aad(&self, aad: &[u8], message: &[u8]) -> Signature {
+        let mut seed = [0u8; 32];
+        let mut rng = ChaCha20Rng::from_entropy();
+        rng.fill_bytes(&mut seed);
+
+        self.sign_with_seed_and_aad(seed.as_ref(), aad, message)
+    }
+
+    /// Sign a message together with associated data, with a seed.
+    pub fn sign_with_seed_and_aad(&self, seed: &[u8], aad: &[u8], message: &[u8]) -> Signature {
         let mut shake256_context = shake256_context::init_with_seed(seed);
+        let mut hash_data = shake256_context::init();
+        let mut nonce = [0u8; 40];
         let mut sig = [0u8; SIG_LEN];
         let sig_len = &mut (SIG_LEN as u64);
         let sig_type = 2;
-        let mut buf = [0u8; SIGN_BUF_LEN];
+        let mut buf = Zeroizing::new(vec![0u8; SIGN_BUF_LEN]);
 
         unsafe {
             assert!(
-                falcon_sign_dyn(
+                falcon_sign_start(
+                    &mut shake256_context as *mut shake256_context,
+                    nonce.as_mut_ptr() as *mut c_void,
+                    &mut hash_data as *mut shake256_context,
+                ) == 0
+            )
+        }
+        hash_data.inject_aad(aad);
+        hash_data.inject(message);
+
+        unsafe {
+            assert!(
+                falcon_sign_dyn_finish(
                     &mut shake256_context as *mut shake256_context,
                     sig.as_mut_ptr() as *mut c_void,
                     sig_len as *mut u64,
                     sig_type,
                     self.0.as_ptr() as *const c_void,
                     SK_LEN as u64,
-                    message.as_ptr() as *const c_void,
-                    message.len() as u64,
+                    &mut hash_data as *mut shake256_context,
+                    nonce.as_ptr() as *const c_void,
                     buf.as_mut_ptr() as *mut c_void,
                     SIGN_BUF_LEN as u64
                 ) == 0
             )
         }
-        buf.zeroize();
         Signature(sig)
     }
 }
+
+impl From<&SecretKey> for PublicKey {
+    fn from(sk: &SecretKey) -> Self {
+        sk.make_public_key()
+    }
+}
@@ -0,0 +1,167 @@
+use super::{pk::PublicKey, sig::Signature};
+use crate::{FalconError, MessageHasher, Polynomial, SIG_L2_BOUND, SIG_LEN};
+use std::io::{self, Read, Write};
+
+/// Verification of a signature over a message that is fed in chunks,
+/// so that the message does not need to be held in memory at once.
+///
+/// The signature part of the norm is computed upfront; the message
+/// chunks are absorbed with `update`, and `finalize` derives hm and
+/// performs the `v = hm - uh` and norm check, as `verify_rust` does.
+#[derive(Debug, Clone, Copy)]
+pub struct StreamingVerifier {
+    pk: Polynomial,
+    sig_u: Polynomial,
+    sig_u_l2_norm: u64,
+    hasher: MessageHasher,
+}
+
+impl StreamingVerifier {
+    /// Start the verification of sig under pk.
+    /// Returns `FalconError::MalformedSignature` if sig does not pass
+    /// `is_valid_encoding`.
+    pub fn new(pk: &PublicKey, sig: &Signature) -> Result<Self, FalconError> {
+        if !sig.is_valid_encoding() {
+            return Err(FalconError::MalformedSignature);
+        }
+        let sig_u: Polynomial = sig.into();
+        Ok(Self {
+            pk: pk.into(),
+            sig_u,
+            sig_u_l2_norm: sig_u.l2_norm(),
+            hasher: MessageHasher::new(sig.nonce()),
+        })
+    }
+
+    /// Absorb the next chunk of the message
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.hasher.update(chunk);
+    }
+
+    /// Finish the verification; returns whether the signature is valid
+    /// for the concatenation of the chunks
+    pub fn finalize(self) -> bool {
+        let hm = self.hasher.finalize();
+
+        // compute v = hm - uh
+        let uh = self.sig_u * self.pk;
+        let v = hm - uh;
+
+        let l2_norm = self.sig_u_l2_norm + v.l2_norm();
+        l2_norm <= SIG_L2_BOUND
+    }
+}
+
+/// Write a (message, signature) record for `verify_stream`: the encoded
+/// signature then the message, each prefixed with its length as a 64-bit
+/// big-endian integer.
+/// A signature that does not pass `is_valid_encoding` is rejected with
+/// an `InvalidInput` error, and nothing is written.
+pub fn write_record<W: Write>(writer: &mut W, message: &[u8], sig: &Signature) -> io::Result<()> {
+    if !sig.is_valid_encoding() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            FalconError::MalformedSignature.to_string(),
+        ));
+    }
+    let sig_bytes = &sig.as_bytes()[..sig.encoded_len()];
+    writer.write_all((sig_bytes.len() as u64).to_be_bytes().as_ref())?;
+    writer.write_all(sig_bytes)?;
+    writer.write_all((message.len() as u64).to_be_bytes().as_ref())?;
+    writer.write_all(message)
+}
+
+/// Verify the records written by `write_record` from a reader, lazily:
+/// each item is the verification result of the next record under pk.
+/// A record whose signature does not parse is reported as invalid.
+/// The messages are streamed into the verification, and are not held
+/// in memory.
+/// The iterator stops after the first error, e.g., a truncated record.
+pub fn verify_stream<R: Read>(pk: &PublicKey, reader: R) -> impl Iterator<Item = io::Result<bool>> {
+    RecordVerifier {
+        pk: *pk,
+        reader,
+        done: false,
+    }
+}
+
+struct RecordVerifier<R: Read> {
+    pk: PublicKey,
+    reader: R,
+    done: bool,
+}
+
+impl<R: Read> RecordVerifier<R> {
+    // Read a length prefix; None at the end of the stream
+    fn read_len(&mut self) -> io::Result<Option<u64>> {
+        let mut buf = [0u8; 8];
+        let mut read = 0;
+        while read < buf.len() {
+            match self.reader.read(&mut buf[read..]) {
+                Ok(0) if read == 0 => return Ok(None),
+                Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+                Ok(n) => read += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => (),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(Some(u64::from_be_bytes(buf)))
+    }
+
+    fn verify_next(&mut self) -> io::Result<Option<bool>> {
+        let sig_len = match self.read_len()? {
+            None => return Ok(None),
+            Some(len) if len > SIG_LEN as u64 => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("signature length {} exceeds {}", len, SIG_LEN),
+                ))
+            },
+            Some(len) => len as usize,
+        };
+        let mut sig_bytes = vec![0u8; sig_len];
+        self.reader.read_exact(&mut sig_bytes)?;
+        let message_len = self
+            .read_len()?
+            .ok_or_else(|| io::Error::from(io::ErrorKind::UnexpectedEof))?;
+
+        // the message is consumed even if the signature is malformed
+        let mut verifier = Signature::from_bytes(&sig_bytes)
+            .and_then(|sig| StreamingVerifier::new(&self.pk, &sig))
+            .ok();
+        let mut message = (&mut self.reader).take(message_len);
+        let mut buf = [0u8; 4096];
+        loop {
+            let n = match message.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            if let Some(verifier) = verifier.as_mut() {
+                verifier.update(&buf[..n]);
+            }
+        }
+        if message.limit() != 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+
+        let valid = verifier.map(StreamingVerifier::finalize).unwrap_or(false);
+        Ok(Some(valid))
+    }
+}
+
+impl<R: Read> Iterator for RecordVerifier<R> {
+    type Item = io::Result<bool>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let res = self.verify_next().transpose();
+        if !matches!(res, Some(Ok(_))) {
+            self.done = true;
+        }
+        res
+    }
+}
@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+/// The per-phase durations of a `PublicKey::verify_timed` call.
+///
+/// The clock is only read with the `profiling` feature;
+/// otherwise all the durations are zero.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VerifyTimings {
+    /// hashing the message into hm
+    pub hash_to_point: Duration,
+    /// NTT of u and h, and their product in the NTT domain
+    pub ntt_mul: Duration,
+    /// inverse NTT of the product
+    pub inv_ntt: Duration,
+    /// computing v = hm - uh and the norm of (u, v)
+    pub norm: Duration,
+    /// the whole verification
+    pub total: Duration,
+}
+
+impl VerifyTimings {
+    /// The sum of the per-phase durations
+    pub fn sum(&self) -> Duration {
+        self.hash_to_point + self.ntt_mul + self.inv_ntt + self.norm
+    }
+}
+
+/// Measures the time elapsed between consecutive laps
+#[cfg(feature = "profiling")]
+pub(crate) struct Stopwatch(std::time::Instant);
+
+#[cfg(feature = "profiling")]
+impl Stopwatch {
+    pub(crate) fn start() -> Self {
+        Self(std::time::Instant::now())
+    }
+
+    /// The time elapsed since the previous lap, or the start
+    pub(crate) fn lap(&mut self) -> Duration {
+        let now = std::time::Instant::now();
+        let elapsed = now - self.0;
+        self.0 = now;
+        elapsed
+    }
+}
+
+/// Without the `profiling` feature, the stopwatch does not read the clock
+#[cfg(not(feature = "profiling"))]
+pub(crate) struct Stopwatch;
+
+#[cfg(not(feature = "profiling"))]
+impl Stopwatch {
+    pub(crate) fn start() -> Self {
+        Self
+    }
+
+    pub(crate) fn lap(&mut self) -> Duration {
+        Duration::default()
+    }
+}
@@ -0,0 +1,343 @@
+//! Precomputed (public key, message, signature) tuples for the 1024
+//! parameter set, generated with `keygen_with_seed` and `sign_with_seed`.
+//! They let the tests of the circuit crates run without calling into
+//! the C key generation and signing.
+
+use crate::{PublicKey, Signature};
+
+// (message, public key in hex, signature without its padding in hex)
+const VECTORS: [(&str, &str, &str); 3] = [
+    (
+        "test vector message 0",
+        "0a7ab426751be3a4544c16cbe66da26054ccb8f5aff81d9a7379d76100992822\
+        abc056baa76512283c92fd9f6435e0c34db5a8e175503910560efdb0a53cdc3f\
+        6829cd554dc368bac43ed3df160f5c827242e6ef95b28edae95ce6e7a6a2d04b\
+        d899d07e8ef0061f142d7bed0aebc9100c96bd9424dedc62b7ce8719eaa3ef03\
+        594b87ae822883b891627c0666af2df809fd89c640927407cacf8226fe1eeafc\
+        055743aca13955c218307952d51695fbbc0602a9db5d1e3ecdcc0b2262b754b4\
+        7644bdc5d4587ad0206de3f0189891529caedc736465cb23dac7bff092b03fab\
+        17b0fe13981c62ab7510d427db5aa01f92be348c29da0e1ecdc7a010140b862a\
+        122ca82399c5cd87baeb0ea7603c6925dd22a89c8749a712271988402814147f\
+        0e697c097395035d201409d344a41db429b18a55231866ef05b4581eb24e7779\
+        2c02e65c8848160848620cf84134956fbc4ec97e82129526b1a890655e09e663\
+        64968161a097adf90878fc6c71a0b0604a8d47235425a68a4b669cb786c46b08\
+        239448fcb8083610c76b755701beef7e75d63b985d812c26a2c6e44d6149675a\
+        eed95806570f84bd273c878278601174f22e214b48c078041becb54571c615e8\
+        4d5a8610d048a6be40a4ca516a999cb4c929d616972d561aa756f0e8dcae1e6a\
+        50dace9c87805ee419924d9df900a0845489b0ea01fa7484d6354c6984c611c0\
+        84862810c300f072123f511080a47fb5345ab49ed564490d55134df78721f4a5\
+        d79e56a7fca2e7782fea0f1deffa4264940a6e25c179ded49855eff34412609d\
+        00e020bd073051181dc11dd0b42afa7f62e0cd663706db273825e956dce0ea23\
+        a9494bbe4c004616a480f5ef9355162b0a2933007165cab9f2df9b80d17d85e6\
+        3dbba5d2ad2d0044929954d77438e2b02f813e41d219aba2a2585e6d848a5fef\
+        ac03e6c686120ddf320e1a7288247d9fc9a0283a4a254c8e2c09ca83279aa2be\
+        607504b337ce8a85e46f21876e54f201dd8372de1910ef07277cba029bc263f2\
+        b89d151ab713ac369a12db31d47c405cf429309a959c12138542e3476d8c7f0b\
+        2aaf58a1eaf0c36e446f74657ca32d700073b948d6ad16f5974e2c5b5268a692\
+        f839126599255500bf11965e8274f0ec528a19f4ba058b28ac651f1458956b98\
+        a8e63ad55c3b684d81812e0826125271b1020191dacefb2c90f7a8aee99fa929\
+        afe0db8a6a00060c623f27e68324ef77faa7963fca0937c353a47b4650f03d64\
+        bd33322ad654ec1626fecf5bd0e12829705583a3e8bf2a56a5f22c25f459f104\
+        4a8b8f666a6a2d58331a528c32010ab508a46ab85cc6a383d1068a60f18468a2\
+        3dacefd9d3892d18f0c742a660b95ae6ffc13713025c0885b35a16d12b11cc67\
+        7a4d3f4f693bf79c50108f79da5925116f49b13480b94593514003872ecb7c8a\
+        c09ee0bd87184c39d5b45d10aa6a5019ab896a8735801ab79ca0c4186d12f2d4\
+        a0ad5726a4e478523343e6d3436def36bab6ec18b79e8a77a6dba8475ba05b6e\
+        faf355544f93c6ea1b9c24544e2173b2508b4d19c2f030c9c9551aa5b5005bed\
+        e91799c3e85e5ce3be55cc3b7f848b024eec19eb073377feaff59ee64ca9121c\
+        00c6253eb795a0d5fb80e89d4c3b9819d271a73137b5c5d8075d567413c845d7\
+        0e01a5b4106cb18e5041a05d61302bd0097079cb3c5ab92a09b4ddd010da9ea1\
+        83cbb733182db2306e0d7ac8dff6464c89b7c501b85f6f3a85e5bbeb9b404600\
+        354424326645b65a9f27cbe0273b62f8b83eddd9a26c271268edbb91894ca8ac\
+        97ec8b1d97b82e409c59d140e9fd9c76ba727e847b8c081a7a84e2ab8c2748ed\
+        c6b612ed2773c112d58106ac4a5d92706d413c76acc1c29d2bc374d4a837045d\
+        492bf05f5a53e083a554c69ae8e2e739123d5889453e82be1d4ae3038f99fe8d\
+        da846df821ae6824055bc192f5e96055497ca1fc2b7a569773a0e02d9abd8424\
+        85015743e0a5a6156e9ec226ce09ad311ea5fb941a6c8aaba59aab440704679b\
+        a727da2054cf1733aeaa2e3816614e6a598650f893ff9fb10310ff25b68de5a6\
+        1cd8fb1686017df14df5de9e89a5ce0a41d291cd35b2c606b858bfa4461fc24f\
+        43253daed2493ad1bcbf22b9686520de934d6442dbacfd5888a649fe65463099\
+        86459d5be260156cea4aeae259c90f0b0501e3a6cd7c34ca075a8bbecca63367\
+        0f04a4541a83abb71f9066badf8610bb5ced0072e2ad0478c672d1188d8dbed7\
+        3817a9b77064ca33d8d5206c2d0317bbee9c5a5ad59a05cb66836ed1d6750ad4\
+        104a8301aa4ea208ca63a9c168563119a858ade29903386d4b8e0f8935111c4a\
+        e2ee869202f67b6bd50024ca56fab645ae3da12123005c7e73dd8f8b3adfb115\
+        4c2cc125d0830abd1aa1470d194de15d58b3e1aaabdd1018a0c8940ed10ea3c9\
+        22a55b426f8b3fa5142dedca07462326b690eb38f71579962a9c9bdcd3cc8896\
+        1e1b2b22013ff4f41b53cde9be55312ad75f353700cff9ca4e58b6cd800175e4\
+        59",
+        "3a00000000000000000000000000000000000000000000000000000000000000\
+        0000000000000000000af8385a3d84ceac9dc44f7b6798852b4a5571e2eb332f\
+        c634423846960c6d0c9241efab7030d57901a97c265a845822274d3bb4666e42\
+        e2dfdaa3ca1bb76cd81d967066a5a85c41b3e4280c199e86975786cf1ac7752c\
+        3c564bcf6e3e2ac6bdfac9b58e343538db75f161b4c31346d99920d73b64b531\
+        bda0a4c97caf14ba0b23dc66e26866d742a6b2e313f4ae4baa8e82d07be0d9c2\
+        a9c285648f19dc7cb9a274cbc89044db7fb6951279b3048f433bbaf62521aec8\
+        c67f691934b416fe69a05054888ff90b47905660a1d277e6120b86389914219b\
+        28191cca88e3266b1dd91960fd7fac40ca37587300662ae9e2d2f82ef0e50df9\
+        b932f327e8bf655bc2d6c493f9fcdb0085acda4a0a4dd5c3340f64263ccc4524\
+        8b8e8ac6d62ce89cdad1ab38f9246d2a8611a33b36e2e574f8a716b9c3b54e3d\
+        cd4ddb4d8ad9b2ac9f5b826777a4516cbe4d6732ee2b4aac76e9e84b386c5f5d\
+        9e6bc0ec638bd34392f8ba98bd9dee69dc55b2ccfede68a62bb212bf55f3c41b\
+        4775dd55d460a94a79c765bdc850aa9edd0fd90ce4105394a2904f65e78763c7\
+        e0f44c4cd7978321dbc89b13b8d6eff4596b020e52d125956068979bae7c8073\
+        10e27272022cc10bede52bebc3586dfcd24442527c5b7536444c9288a3220425\
+        a86d5c1a7f2085f038f12573c7cd89692e86ab56e49da171a6fcddb7d7407eeb\
+        1fe5574d9660102682b58e56b1cf272cf2f62274ea9b24bec453e577b95ef9b8\
+        7075035e2110892d90bdd53c56aae69acb6ddabe6bb3db2d321cb717f31c24fb\
+        5e4b1e7e91f8d7ef28a725cb17df72710f4fa7d39cb3dce28b0b14c1846130bb\
+        63d22acf6bd59df7619a3e99b63be85ab3141968059eab6893cc8a6f7e70e42a\
+        3ce22c455394a124350baa88692e1d073133b6dd909246b04d236c8e44b9af35\
+        177e04a265ca33b543747e358b79884dcfacb3005b3a9d4420ba2ba2249c29f5\
+        2abfc1b9369ad68ed0cd11ed02f198f664d0cdfb1467c8f109f5ab9c4397f735\
+        6fc98140f4b3e65cbddda7ea8d7ac7091902388760af341baf1683223df31aa4\
+        32be97b90e8bef82f0cd5489678e5b185158d3812f30e4b2067ade185a2f16bb\
+        de78fcffb1b774127a1c5da783a554a78963e723dc4234f2c50ca385b04f896e\
+        15b713c9e5227982e24df4b6bc82413c7ba18b8f4a45b977bbb2071737e3c9f8\
+        129d6605382138eb25a686b399ff891bfcf799a881037ae0508f62d18f885d11\
+        2de7b7eb75efae9c3b7d3ecfd23384f4df9864f14d4f8f43a9cad26de02dabbc\
+        b0aa16f18acc22b6662cbdcdf0ae9a7199986b64c409f8ceb4ca246d5d24951d\
+        6ee8f0ced7123cbba7105b5b819ccf330d8a7b6aa8e5a581c1dd9c6c733945de\
+        f2b00ee3f0deeeb3904f42d44abd0832f8cff55ba4ba51a59d4fba78ca2792df\
+        e0426b55c81293b9b435cdea97f5cd4c74bbdc6a6b4788a3d7879ab088f2d5d4\
+        f44e30eecaa5053db3932ac2756ec4c99058183691dbc2a68d955f926a7291bc\
+        3e16d19e572ad27213c4b625ab52ff0b78b06a031e823838e664652f6d370ca7\
+        ebab2c4bc7b159af8c3146b34b7d0b8f9ad4b8a6c60d7ffd76d3c9240f37e735\
+        ddca8c8d04c51cb7528f79e55675749c879c5b280e25ca10655c6e02974e52d8\
+        1b870d6f24f0490e2d982f928894a97ce1eda72b76a5bd415d79af0a9750785e\
+        deef3bdd4082055a90c29fcf2a7a1a6a51d1e5ad6d441d4f608de7bd",
+    ),
+    (
+        "test vector message 1",
+        "0a12b99dc493638ca9f108f875c37474186c3337e87d1d2ce6e950ae67219a09\
+        1bcb556f90b688338887780e656484d8de864f3c2f3489e1f63e9227290a4a3c\
+        4f3177088165d9a5e92bdbe69aaf979dd443210fa0aac0bf62bc062f96aaf6bb\
+        a4853695f95efb8f99cd320553cbcdc4d839099088cc97ee2775fe751cdbca77\
+        3814d2b1a4816d94cb05129a82935a918bde28f473cc35d4d51ca74ce453896c\
+        96ca51cec7818ac2e251a4e8d31067058af5dc6259d13ab88cf658b4a0da7e8a\
+        16f69f0e8e1ce87c698e563b7a75afc5742c5ba0c66ff0d12fe26b64e4f45342\
+        5a2b081e688c9dc2a046b6ba46dc65a44a01221a82101915b352d0e29920d073\
+        74a11c3949b186a175ace147324057a8c0318d51a388be2d4f0e4c1c8a1223ca\
+        75dda8a7f411ad91f6b476f3a2df41681571d15a11841cbfea31d68000154e6a\
+        e302f99bec01389727fa808895d137672e187289059092432b183a280102eb07\
+        4266a1f1cf48aedc1e199a55ab4a7e827bdcd906ad7d553bfb512b7ec07438b6\
+        5c1e9ab94312435152873dbec9f266573350f9b6941a8e2fd59df504d7cf8c59\
+        0b96a3a73c1502237598e044abf5468726d696110daa7b7547b27ff09dc181e0\
+        3c2405b7e269482ab8ea3247c8014db554a5b09d28f53a65657b716e64a0d493\
+        23ced5811f05f5193d86009679e19be4d9e10ec20af089062863dd2e35072a31\
+        026c65e09917f01bba4b738507bf13b524e11e238f98f203d2a98f54b3ec207b\
+        76a24060aed8b1bc4abeba91ac187ad34a4d0d5a990f1339096c5065f41b0ab8\
+        08823601eb4222d5f89979592e273412dadb93c29bda668b338d2d3ec5d8e3d7\
+        6b5b492d347728105c1ab95725006f34314de9463fd97b375493d6f1601a618e\
+        b793182bdfa9bd1372e25460023c8a59a5dc80500c0e1ae604c348946535394d\
+        4f2b8ad0f1f041c836a08f0427de08b7f6c3bb9b9e7428c5b1b79f652268a63a\
+        727410fb59926c2b219ac96122f3e121cf561f950d482ed7e79e2ae5305044b0\
+        345e3ab81e2072780237d2b70b5eb320ef74b4afc52788c2665424bca30aa1f8\
+        da55205f0aaa8533dae248f106d3781cef87605f840e1778915e7fa618e3c351\
+        f05a35371277878e7f74de6d322b29f011814a8bbc88b5a47140932020310988\
+        4de2536d5f53270ca22850a9541fce6d31a2ca54b46822b29e1835bb26c46eb8\
+        bf6a7c24e010750e13a5dab06214c767b2d9df5c1675c7e410d46fd2ce902610\
+        87846c9f04c0cf42ad5487426b13021020af3165c96eaf899e22bdccc23a6c03\
+        e58eeb4b021653cac3493809b5a310ef575783013300c926a766ac294a7ac403\
+        15513203878836292129e9bc899db5ccdda2258a874b5e954521ecd538949088\
+        e00a4e5ee498963b2f06ba2028c6278010688cbb69825aa339a60769ae1e7b15\
+        5a0b29cb2c3b792d3d28b72c6c3ca9677bae4d7361c4e0a17a6d58891ea4e8b8\
+        55e59a125871a50ffa262de5b35e638ca362945adf141f1368af88010266763e\
+        8d374a5ef29c8e96b48d545d05c4fdbab4d39d74e6d4710a65e76434fd23ba63\
+        16513208d358c70a154a460742a3e800d418293aa21783a93d2a67ac9012ce82\
+        eaa5a7f326c01b872b1e4ca744fb8a101bfe974148eba55d37bc692f12e44b89\
+        8a71a544446d13689ad8b88a2a0948f6bc865ad1abb612be69570b1172d12aab\
+        7b2fbb929e9dd2b9931d59ea8230bea402b365f2503cd8829b10ef8ad2559c65\
+        3510a4c0215612c100bbb8c7fb0d68e0b2cf52665ffed825f8a05d7535ba56a5\
+        65cf9c50f7257bc438a8c0c3c5548d36728aff01b04da6b8b8a7b21adecaa63c\
+        45b92fd753782c74c50b58e1324de15a366aebaa51dde08a6077aff02a5b2bd9\
+        d5205633891ec55073d4eee5ced21c49d86497f744449b0af2691606a8a66a70\
+        b86e1bacbc2574dafe9d876264589b4689eb876533a342d3a234f53b443e42bc\
+        68b6e20a8055c2b551a743c5e26069bdc78517df9898f9b37b52ae614ad44c93\
+        52dea83c9dc0028825c78da96543caab247fdc6776a38a028da84944f9566b38\
+        c85269d62c471ebc6b671425467ba5a7620a2071283a70b27342419f5ad327e3\
+        e2d0823ce9e0db155377d8cf0a5a48d75d904177ac0b2399c07ed0f7075eaa6a\
+        b9a2939c45998855a55ea53b491c490117ecae9ccac3a5e402e2ba25ea99ee93\
+        c82e78401a7817db391ce05b10ca6a9870f558f613cd90854fe17e1e86a2d9a7\
+        83b9964d3228cb9bf48a32a3c622368352a4523cf56013a867ad29b9b715602c\
+        4d501499a80276a22901286f24b885cc75eb9b1bcc9f890c106647df01f19087\
+        93c4c64fb0af562354d27f30bc750a45a20059f0db4661c473faf9d276dbc699\
+        d52e607388834f81eb38ea279227e0c9b393eee43a12b3c3d4c52f8662f778fc\
+        0af969e498929963177d4a7c3669a2807a794ef3b1a8668d8ec4a68ee5da8c2d\
+        4d99bfcfde8fbcca6854946b59adb7a858e21725462988060abc9945bc89af88\
+        48",
+        "3a00000000000000000000000000000000000000000000000000000000000000\
+        0000000000000000001e4b967717b304702565027ed6ed669ba33034c81b311c\
+        71ee43809721375b010069e2b8cc4a3164a82a5848ac591f7cea0bacdc816098\
+        9acf2f59144d614dda00cd6836f6ead7c73f5432d2344f18276486b78f57b0ec\
+        93532e3c5004e6d9adc54f98ee5e5d81ae14764554e9c652988a87534b9a6813\
+        93fbd374dc080b1daab023f3293e73329fe36599554539fe41b91d0d9686dd2c\
+        77564b2d833f3d6e7ffa2eaabcf0239c89a4524548e638c6e13dd6457bd3cc9a\
+        899466e7b44d170624b3b36c8c8d366e0e05646f4f23c2c6eacd7baf19d75f7d\
+        95bcc21eb5bc3aac3247da853dd60c9ed194280f05c4c263cd7ec3d52d435aaf\
+        0ad73366a1ee09b310449b72b667a6d4b7f6092961d71506333c132bd2c7ffd5\
+        294b6a29cc32fc45162eed5dca74ac7bbed1352b933e4cd30438ebbfb5e43365\
+        bb8eb417c83b51b8c779d355c30b09e3de120dcbc78a51a65cc56f52d73c0d14\
+        ada6a5b0894b5893efe6078a2df2d24b533a928cd5a4c28d017478880c05022d\
+        6eae2f462a893d8effa4eae64c44dadfe76cbe465748f1d0e0089aa4f366de84\
+        5a08a7733729a66d066ea366b70355c238faf41899f0d3cdd2bacee79ba920d5\
+        3814a295c6a7cba22f04f397a6b448ff702d3ad6652ed924ce9db98a8a6946d7\
+        eff59ea6c2e365998a02928ab9f86695c869530b23195b41da0573e7b6cd4498\
+        85e2ccc93546c52947713ca59d2275606db63fc6865618c7f49470f731d86191\
+        676673ff89b279da27331f31619eda6cde8f1e3c9e34ed193b633df3e574d907\
+        31b3bf7fdb6b0e96359f30fba311dff21fdb6c9964f9935b0c8d9c89a7949cf6\
+        2270c9ac7a624ac3e2fb7b132264dbf53a1cca4cf6a67b609e49f76adee6cc76\
+        200cb3f7dfbcdeaefe1fcb2cf04a73bb5a5b95ae28c884db66448839806160ae\
+        4eaf1fb74116b1ac4f77493225b7dd156e3c161ea9f0539802c2fd3b88ef671c\
+        468eda985ad3e480937770c6770e3d49fb2e8ea7fa1bd3ce9c775c2a4f3ae8fd\
+        63e0f9d7cb82ed485151bebe8b5e58adf92ee470a133d1dcb2b8ae5f3be92a62\
+        9cb1901d2a78cf3b57fc80872cb4ee56e9a98968690fdc869374c8b8892b4a3d\
+        1b3d76521af0c30e02e3dbc29bf67fbf5a99ca8c6a6b9351c9eea1b7742f1973\
+        e6ca1ff78dd9ac29dec8a63c9720f7e32d0be14955e893999469649a4e32651e\
+        7565ce542f0bfde7beb5e4544188124d0355e6f0c54666f3b6e7fd7b49c8b939\
+        8fffdf649209bddb566f2efaa6cfb08e8e26698a24db0d5bc1e9b2ffb4defa0e\
+        3cd3b94c0c36c5689c6d24f816ee615a76361168823f466ccd2e75b6d5c9202d\
+        d387db48dbecb687c30718bec381b55a5d0ed5dd7b42d2cecd6cbf1bb20f8d4b\
+        1c88ef6a857df03139e4818a64f52c7f8d5bed05b625009ac147f3f2e2c4c41f\
+        d4d6ad5c9a426c92cf330ce3f8966fe3ea25c78361ee77b58c7f0b20e6cc3873\
+        4fa7b0b1c2f07c96d7cde3edd19854210aa7f4341e88f7e1bb60eb7b2a3bcb08\
+        8638d4bce8eae2d0798d8953ae77db0d1eaf475d7fb9c95ebbadd77730de4a6d\
+        9aabc8a43185b2a3d7ccaa4869b9a4dd50178cc2b759048315f0471aa6c54c41\
+        65d5189220c013054a81e6613ab5ae3ffb2cb1b2f0447b1fc6d0779765661262\
+        975e2469ae18f81eef52f8c6f8f1f650c110e3e14c27ef6dc78899e164751442\
+        e9482418a97b75508f4bbaf1147f5dcff96cff64",
+    ),
+    (
+        "test vector message 2",
+        "0a29e94f2885012a09e020d86d40233531f6ba86868791f936600fa2d00b9df7\
+        14d46cce4a34f128e454e61644e7884ac8a832d65caa55160705247600055acf\
+        1f357ce00a00df177cee7a1927868f14eba1e5986e9a80a8e4f78e5f5f38be2b\
+        68d7be8100a2b80ae0d82026dd57bd0c0536a80662959d5c88ce9927519ff33a\
+        16ed999d5c624b1268f1f182734bac3f553b405485808f4b92814db88aadd86a\
+        0a6d62fce911db65cb72364717399cdf127009946de87a90e139d500a3007d80\
+        96b4820167496174d54293006cb1c5706dd98a9bf29c00cae5d74dc5dee9e384\
+        0a2362eb1a71e051b356d37b5edae037d83f14060ca7ac90ae771d9319854d1a\
+        8b465fb17282e5d20c137e0db020f4215aa70abcfb76f39c5f6e5172d51882c6\
+        5ae09cc83fae8b99ea65148910c10691f9d14746e6522c7e8938d594a7997c28\
+        e55ebe0395563b4a04f845a5a4b50bdf9a9d44f881048eedb6e0c670efa918b9\
+        609bbb1f4c2f687db637e5a29689905205cd61fc30819f31538fb718690ff18d\
+        c6fd1831fc7b2b5d704da2c65248efa1ac6d53780c4e5e8d1c65943415c49dd2\
+        385b4245c6ad79e31a770e1bb422f207b1e71e25960a42b9c7f11cbacfb71de6\
+        c479a91f118feb18826e68304545386835a388591749011674b497533f8f65e0\
+        19442eef5c36e6f01d811b7926eb598500e125227785d944a26f3cd254e61faa\
+        a1d8f5d900cec393f21f489face7a595fdf417db778a2a2715ff64fe85821274\
+        ddea3974c4efa63690544adca5c6f346a14d55d97177974e1144c1a11fa7221e\
+        d4a1003b2b259571ee57c29f6f4a92759b2d5cc904e2183467a38e69f4684ab1\
+        1264bbe586265ba22f99e03db1b4ca3c7908199936ca2b9402cf679e67701710\
+        75a6f64d3c2fd04a87f585efb1f2194868e6acbda6e1cb10c561a5f6d2875844\
+        dc585cc5972b9f9b8f4e2e3797119bb2023f8682a3818c6a566079518bbf00b9\
+        f1c69c24ab48d687a95648054c60b13acdb466f82a87a16f58b92eebb19328c5\
+        5b5a8337156e0e6416ba11535ca54694a25183c6f182caf3f49c62566d8800d2\
+        0842b921765abb29ad1e52666471638d0e668283a38987964761880af96ce32e\
+        7da058a9ee504c95475b135cb271b0813b81249444b8bcfbac24f4ae3c3fa8d8\
+        1b0e87b97dd9cc5f292ece5a457a990864fd537a2d51bf6facfc4ba7e4eb874c\
+        6f0a70a6a200214fc3a6697278e84218ec8e2487a58c27474fcd7875bddb6312\
+        c2857118a01408f51c51559b5a5400024d22475a4ea6432e7306e923224036ac\
+        f20fc490af087c54deabfa2dc8cdc8ca0852758e0750205c73ab69f7ea1d0da1\
+        bf404f068c54a4af4052a5c3a45c4b70e5fa638c39a3708095c7cc39980aca55\
+        05608920f8bf44f8c5b493292766ac4668b5921524596b31500050abad02fe27\
+        0dae430dd9ff768c74f2d49cd318bd7b6a3c10c5640e9fd0190500479cde50cb\
+        410b0834501238c7a01ca6b254c26e6c00b1db4a88409c279d04d0e528746409\
+        30abf7e7fcb8c83f35538b1e0ae637e9d30ec58329b0655a180560800f51779d\
+        221e98933b50ef2f9a5653298653fc5416e2113028aab429576199df1fad449b\
+        8440cd7a9b4d6936244c36313dbfb2cceba305da8314b1a2ac96cc3c77668cee\
+        05b973181f4c8e06e286f9eede66037ae1a8126d590b75eb81601801af08ac77\
+        4f56aa9870b9f1d55a323f12fe31dc08f27b2a0284f5e6e1aea5a5481e043907\
+        5a8644b795d3b03095430292eb48c09897d3a1e31e6add043b23b1a759cd045b\
+        95eeb7ecd1b749944d345e0a75e110e7830206227e6bb256bce634c52bc21810\
+        2b9127640f4800cc53b28f4103746df2bd69be69d4e5f7a76243723603246e4a\
+        d9a3c173e87c2247a3308ae20028eeaff22832a903fe9162c38346aaa7b3be9f\
+        722988455e9618b7d39a640ebe20528201c26ff9d9a9ed19980aa03c220cc51f\
+        3850e6a9d56c487fbde6c6b1afae64f0bec07c2e4f199ae3782fa5cc9cc1eb04\
+        039bc409be1e07141c16b901f4bbf4acf648c682a3489d9a502e05e6c452e91a\
+        141daa136d4fb27df4fba3e4119b34fc65222129783c2df65ed0018662a7b947\
+        40ee028cbc05cfcb7834d1187aa55ed1621c00a5249259aa923f3aabcc1f8acc\
+        e5f68e6471685861f097e2745c401c43d94d688d5a68a92fd41286d5c2f0911f\
+        ffbddd0f302bd49147fe2892d82ab69e1aa4d1418ef76541cdb612cf2747ea81\
+        04dfdfaf9194bc3151e78f8930bb33c45d4ca742d5f512090dba24829aa0dfba\
+        4cee450ba1cdf3b9a4bb5b24d5761f984c6489416f64b8ab37c816d3af80a846\
+        899f3f48def6f75460a090f0f2b2ca1c3f89126c1bc3ad615001f4a28de89645\
+        5a2fe337c9d6016136da04c8589c6e09b7d329b5895838d1c2463468111339db\
+        6e05b8f04cb571df6a5114f6269bc6ffae6e00d9c168e4a55acca728ce53444d\
+        8da860ef988798cce46f0f1826d85c5672d5f6904d619b7722b04aade4f79e49\
+        b3",
+        "3a00000000000000000000000000000000000000000000000000000000000000\
+        0000000000000000002aafe734ca2f5251fe64602e43546919882baee9357d42\
+        71521b343e986050047b898b375bcd374b7eabc1e3edcf4933f061a9a4be0f2f\
+        456a58884b43958d354afccdf784b00ae5b4a831bd965a7ac035f44817b11a69\
+        a50a3aef0f6fcb6b0082921c0f2f7b3a7ed642509fcb49ad891e45553679e78c\
+        b9bde58a55f39e9efb4f82ebcb4a5f48af4cf57dea9c7ba1108b63ce3acb05d3\
+        dfc98cee1519f0b6bb7534b3bc9bbf874d7972d8fdc4f331a0880bdc5b87d943\
+        71cccf5b78d01adf2ac9991f5ab201065c733206eb0f224f084c921eafc5f2b2\
+        c2a2957b747a190523270bda4cdf24e1481f347fc5de9e234edc954e84c69b36\
+        d1c36e5c5c4b163c102eb51be70eb6865d67a6c95d3609b5e14e916d14932ce9\
+        6268cfdd3674ad414e46b12185ffa44c4f85d05b4e52abb2745eaac2db267916\
+        1d9b7e7b6d8f3476f39f30b0fa523cc3b9b752d5e0c5b2f536d4f09595eebb24\
+        ff2c3f893c13e3a97cc8e99948ae72d650ccdb27c5616928d45c1da4bfaf7116\
+        1bc27929e8be9f9faa7cfb12640cdca6e7c74f9d829875a58e40cd1a4eb651f0\
+        a6e3b1216aeb6d82ab437d5cc392a4562983cedfd53970384b449f744ca7fe79\
+        ad8f3650bf76608bb2b04993812a4feb7846554d5f89d3f51ddc67a1736384e7\
+        f2664ae148b344aea86d825cddcceb2ca1bf6b5259bf164a98ad507be7176d8e\
+        80f91d3d3d7d5dedb439a1e466e44aeb765d61b9cc4c1169f1ef22ee2a318ed5\
+        5894ec3b6707acd1dd17e903326829e2553311f79f24f73c863b0879a2890323\
+        f55c9cd46e6ca2a4976e9d290b65498e4ce937382da6a217df61b72eaaab6eba\
+        6973095ec769882d4d2ba4ae774ce9fd5dfefd984e561d232770cf1f29a271cd\
+        f6d9586c084dde998cc3c8353b8648e1ad547d9c7d93dc3873672bf78068f29d\
+        bbcca5ca491b42efbc47dd15508bc9d2e4e7fd9dd15a7eb1b31f8cd820789a91\
+        45405844779ad538fd9d5cd12dc6e62a6ac7e150f3c69f8a791ba7b48e84fa03\
+        0d67bd5aa67ad7ac915dbd5e9dc2f9c3bb6cca19c666e37b46d530b739b1e5af\
+        4ea2662f32e4f93e97cafd90d6116525e5f6f68fe08762f4ec539bae6fa216ca\
+        9ba5d9bb6a1a94ba12632436e6850a4cecf6770106d599872d2614f851dfc540\
+        b4eb7b80e6796075314643f199fc4b65da6a65d70e5bb0936c6352d77a5c99f3\
+        5e92d2339c76b959b4e5d04b46f104d8fea091ef5de51126947da9ac2a71ee02\
+        cae19486b27ea1386641a467ddc86153a476ae093c41698143305f2c0196c3a7\
+        325bab4fa0aed49a24d04c9a199b2f14cd2147be27f4ec4ce9da4d649d21234a\
+        53b8e1416833df33aad2e01a17e7366d2227d961a93389f7624e6c60d027121a\
+        92594d0e0135f57792bdb39543d97f219851d54d718d2188701862ed36cc5624\
+        a792dd1e60bd2edf27d09363dd1504cbb92a120b86eeaaf9f608b25fe9fabfd4\
+        898d2f49345f46ab6d54ee5c41df8b66ecf852dec7ca3744327302534fd7fbd0\
+        95c504ba13d25118f6557fec2533c3d42369521aa3b6f73885427f624e483a01\
+        b1c520ec38a0e4d7adb68452cc7120582c0876e75f019ef534a4f5374f902f42\
+        598baea08f6dcc829e8312fa6ebf1a084a6092ac8c8e5a1a9b5c257ce71306e2\
+        be33179b798664260971a6946df51e66d1a31f45708cd3d95c1c40e030eadbf5\
+        be5cce5e679ac1b06ccdc73530c021e9f51fab068f157874",
+    ),
+];
+
+/// The embedded (public key, message, signature) tuples; each of the
+/// signatures is valid for its key and message.
+pub fn test_vectors() -> Vec<(PublicKey, Vec<u8>, Signature)> {
+    VECTORS
+        .iter()
+        .map(|(message, pk, sig)| {
+            (
+                PublicKey::from_bytes(&hex::decode(pk).unwrap()).unwrap(),
+                message.as_bytes().to_vec(),
+                Signature::from_bytes(&hex::decode(sig).unwrap()).unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_test_vectors() {
+        let vectors = test_vectors();
+        assert_eq!(vectors.len(), VECTORS.len());
+        for (pk, message, sig) in vectors.iter() {
+            assert!(pk.verify_rust(message, sig));
+            #[cfg(feature = "ffi")]
+            assert!(pk.verify(message, sig));
+            assert!(sig.is_canonical());
+            assert!(!pk.verify_rust(b"another message", sig));
+        }
+    }
+}
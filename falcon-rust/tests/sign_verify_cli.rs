@@ -0,0 +1,56 @@
+use std::{env, fs, path::PathBuf, process::Command};
+
+// `cargo test` builds the examples next to the deps directory that
+// holds this test binary.
+fn example_bin() -> PathBuf {
+    let mut path = env::current_exe().unwrap();
+    path.pop();
+    if path.ends_with("deps") {
+        path.pop();
+    }
+    path.join("examples").join("sign_verify_cli")
+}
+
+fn run(args: &[&str]) -> i32 {
+    Command::new(example_bin())
+        .args(args)
+        .output()
+        .unwrap()
+        .status
+        .code()
+        .unwrap()
+}
+
+#[test]
+fn test_sign_verify_cli() {
+    let dir = env::temp_dir().join(format!("falcon-cli-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let file = |name: &str| dir.join(name).to_str().unwrap().to_string();
+    let (pk, sk, msg, msg2, sig) = (
+        file("key.pk"),
+        file("key.sk"),
+        file("msg"),
+        file("msg2"),
+        file("msg.sig"),
+    );
+    fs::write(&msg, "testing message").unwrap();
+    fs::write(&msg2, "another testing message").unwrap();
+
+    assert_eq!(run(&["keygen", &pk, &sk]), 0);
+    assert_eq!(run(&["sign", &sk, &msg, &sig]), 0);
+    assert_eq!(run(&["verify", &pk, &sig, &msg]), 0);
+    assert_eq!(run(&["verify", &pk, &sig, &msg2]), 1);
+
+    // a corrupted signature is rejected
+    let mut bytes = fs::read(&sig).unwrap();
+    bytes[1] ^= 1;
+    fs::write(&sig, bytes).unwrap();
+    assert_eq!(run(&["verify", &pk, &sig, &msg]), 1);
+
+    // missing files and bad usage
+    assert_eq!(run(&["sign", &file("missing"), &msg, &sig]), 2);
+    assert_eq!(run(&["verify", &sk, &sig, &msg]), 2);
+    assert_eq!(run(&["sign", &sk]), 2);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,2 @@
+//! This crate has no library code: it hosts the integration tests that
+//! involve more than one of the falcon crates, under `tests/`.
@@ -0,0 +1,106 @@
+//! Cross-check of the r1cs verification circuits against the native
+//! verifier: for any (pk, message, signature), each circuit has to be
+//! satisfied exactly when `verify_rust` accepts.
+
+use ark_ed_on_bls12_381::fq::Fq;
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
+use falcon_r1cs::{
+    FalconDualNTTVerificationCircuit, FalconNTTVerificationCircuit,
+    FalconSchoolBookVerificationCircuit,
+};
+use falcon_rust::{KeyPair, PublicKey, Signature, N};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+// Whether the circuit is satisfied; a gadget panicking on an out of
+// range witness, or a synthesis error, counts as a rejection.
+fn is_satisfied<C: ConstraintSynthesizer<Fq>>(circuit: C) -> bool {
+    catch_unwind(AssertUnwindSafe(|| {
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        circuit.generate_constraints(cs.clone()).is_ok() && cs.is_satisfied().unwrap()
+    }))
+    .unwrap_or(false)
+}
+
+// Assert that the three r1cs circuits agree with `verify_rust` on the
+// input, and return the common decision.
+fn cross_check(pk: PublicKey, message: &[u8], sig: Signature) -> bool {
+    let native = pk.verify_rust(message, &sig);
+
+    let circuits = [
+        (
+            "ntt",
+            is_satisfied(FalconNTTVerificationCircuit::build_circuit(
+                pk,
+                message.to_vec(),
+                sig,
+            )),
+        ),
+        (
+            "dual ntt",
+            is_satisfied(FalconDualNTTVerificationCircuit::build_circuit(
+                pk,
+                message.to_vec(),
+                sig,
+            )),
+        ),
+        (
+            "schoolbook",
+            is_satisfied(FalconSchoolBookVerificationCircuit::build_circuit(
+                pk,
+                message.to_vec(),
+                sig,
+            )),
+        ),
+    ];
+    for (name, satisfied) in circuits.iter() {
+        assert_eq!(
+            *satisfied, native,
+            "the {} circuit disagrees with verify_rust ({})",
+            name, native
+        );
+    }
+    native
+}
+
+#[test]
+fn test_cross_check() {
+    let other_keypair = KeyPair::keygen_with_seed("another cross check seed".as_ref());
+
+    for i in 0..2u32 {
+        let keypair = KeyPair::keygen_with_seed(&i.to_le_bytes());
+        let pk = keypair.public_key;
+        let message = format!("cross check message {}", i).into_bytes();
+        let sig = keypair
+            .secret_key
+            .sign_with_seed(&i.to_le_bytes(), &message);
+
+        // a valid signature
+        assert!(cross_check(pk, &message, sig));
+
+        // a tampered nonce changes hm, and thus v
+        let mut bytes = sig.as_bytes().to_vec();
+        bytes[1 + i as usize] ^= 1;
+        let tampered_nonce = Signature::from_bytes(&bytes).unwrap();
+
+        // a tampered coefficient, still a short signature
+        let mut coeffs = sig.coefficients();
+        let index = (i as usize * 37) % N;
+        coeffs[index] += if coeffs[index] < 0 { 1 } else { -1 };
+        let tampered_coeff = Signature::from_coefficients(sig.nonce(), &coeffs).unwrap();
+
+        // a well formed signature above the norm bound
+        let over_norm = sig.over_norm_for_testing(&pk, &message);
+
+        for &(pk, message, sig) in [
+            (pk, message.as_ref(), tampered_nonce),
+            (pk, message.as_ref(), tampered_coeff),
+            (pk, message.as_ref(), over_norm),
+            (pk, "another message".as_bytes(), sig),
+            (other_keypair.public_key, message.as_ref(), sig),
+        ]
+        .iter()
+        {
+            assert!(!cross_check(pk, message, sig));
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Differential tests between the r1cs and the plonk verification
+//! circuits: both are built from the same (pk, message, signature),
+//! and they have to agree on whether it is accepted.
+
+use ark_ed_on_bls12_381::fq::Fq;
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
+use falcon_plonk::falcon_opt::FalconNTTVerificationWitness;
+use falcon_r1cs::FalconNTTVerificationCircuit;
+use falcon_rust::{KeyPair, NTTPolynomial, Polynomial, PublicKey, Signature};
+use jf_plonk::circuit::{Circuit, PlonkCircuit};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+// The gadgets of both backends may panic on witnesses that are out of
+// range, rather than building an unsatisfied circuit; a panic counts
+// as a rejection.
+
+fn r1cs_accepts(pk: PublicKey, message: &[u8], sig: Signature) -> bool {
+    catch_unwind(AssertUnwindSafe(|| {
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let circuit = FalconNTTVerificationCircuit::build_circuit(pk, message.to_vec(), sig);
+        circuit.generate_constraints(cs.clone()).is_ok() && cs.is_satisfied().unwrap()
+    }))
+    .unwrap_or(false)
+}
+
+fn plonk_accepts(pk: PublicKey, message: &[u8], sig: Signature) -> bool {
+    catch_unwind(AssertUnwindSafe(|| {
+        let mut cs = PlonkCircuit::<Fq>::new_ultra_plonk(8);
+        let witness = FalconNTTVerificationWitness::build_witness(pk, message.to_vec(), sig);
+        if witness.verification_circuit(&mut cs).is_err() {
+            return false;
+        }
+
+        // the public inputs are pk and hm, in the NTT domain
+        let hm = Polynomial::from_hash_of_message(message, sig.nonce());
+        let public_inputs: Vec<Fq> = pk
+            .to_ntt()
+            .coeff()
+            .iter()
+            .chain(NTTPolynomial::from(&hm).coeff().iter())
+            .map(|&e| Fq::from(e))
+            .collect();
+        cs.check_circuit_satisfiability(&public_inputs).is_ok()
+    }))
+    .unwrap_or(false)
+}
+
+// A valid signature on message. The plonk circuit additionally bounds
+// the infinity norm of (sig, v) by 765, which an honest signature
+// occasionally exceeds, so the signature is resampled until it does not.
+fn honest_signature(keypair: &KeyPair, message: &[u8]) -> Signature {
+    for i in 0u32.. {
+        let sig = keypair.secret_key.sign_with_seed(&i.to_le_bytes(), message);
+        let sig_poly = Polynomial::from(&sig);
+        let v = keypair.public_key.recover_v(message, &sig);
+        if sig_poly.infinity_norm() <= 765 && v.infinity_norm() <= 765 {
+            return sig;
+        }
+    }
+    unreachable!()
+}
+
+#[test]
+fn test_r1cs_and_plonk_agree() {
+    let keypair = KeyPair::keygen_with_seed("differential test seed".as_ref());
+    let message = "testing message".as_bytes();
+    let sig = honest_signature(&keypair, message);
+    assert!(keypair.public_key.verify_rust(message, &sig));
+
+    // a tampered nonce changes hm, and thus v
+    let mut bytes = sig.as_bytes().to_vec();
+    bytes[1] ^= 1;
+    let tampered_sig = Signature::from_bytes(&bytes).unwrap();
+    assert!(!keypair.public_key.verify_rust(message, &tampered_sig));
+
+    let other_keypair = KeyPair::keygen_with_seed("another differential test seed".as_ref());
+
+    for &(pk, message, sig, accepted) in [
+        (keypair.public_key, message, sig, true),
+        (keypair.public_key, message, tampered_sig, false),
+        (keypair.public_key, "another message".as_bytes(), sig, false),
+        (other_keypair.public_key, message, sig, false),
+    ]
+    .iter()
+    {
+        assert_eq!(r1cs_accepts(pk, message, sig), accepted);
+        assert_eq!(plonk_accepts(pk, message, sig), accepted);
+    }
+}